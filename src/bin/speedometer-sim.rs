@@ -0,0 +1,426 @@
+//! Runs the dashboard on the host, drawing each frame into the terminal instead of sending it to a display.
+//!
+//! ```text
+//! speedometer-sim [OPTIONS] [FILE]
+//! ```
+//!
+//! `FILE` holds one sample per line as `SECONDS SPEED RPM FUEL`, with `#` starting a comment. A hand-written script can
+//! space the samples out, since values between them are interpolated, while a recorded log replays as it was captured.
+//! A `FILE` of `-` reads the samples from stdin, and without one a built-in drive is played.
+
+use std::io::Write;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use speedometer::dirty::FrameDiff;
+use speedometer::draw::{draw_arc, draw_line, draw_rect, fill_circle, fill_rect, polar, DrawMode};
+use speedometer::newspeed::{DisplayArr, DisplayArrErr, Rect};
+use speedometer::segment::{SegmentFont, SegmentReadout};
+use speedometer::swap::{SwapChain, SwapMode};
+use speedometer::term::{Braille, HalfBlocks};
+use speedometer::text::draw_text;
+
+const USAGE: &str =
+    "usage: speedometer-sim [--fps N] [--rate N] [--loop] [--braille] [--frames N] [FILE]
+
+  FILE        samples as `SECONDS SPEED RPM FUEL` per line, or `-` for stdin
+  --fps N     frames drawn per second (default 20)
+  --rate N    playback speed, as a multiple of real time (default 1)
+  --loop      start again from the first sample after the last
+  --braille   draw 2x4 pixels per character instead of 1x2
+  --frames N  stop after N frames, drawing as fast as possible";
+
+/// Drive played when no file is given: pulling away, cruising, a stop, then a run up to the top of the gauge.
+const DEMO_SCRIPT: &str = "
+# seconds speed rpm fuel
+0     0    800 80
+2    18   3200 80
+3    22   1900 80
+6    45   3600 79
+7    48   2100 79
+12   62   2400 78
+15   64   2400 78
+19    0    800 77
+21    0    800 77
+24   55   5200 77
+28  110   5800 76
+32  160   6400 75
+35  120   3000 74
+40    0    800 74
+";
+
+/// Fastest speed on the gauge. Faster speeds still show in the readout, but pin the needle.
+const MAX_SPEED: u32 = 160;
+/// Highest engine speed shown. Faster engine speeds show as this, since no more digits fit beside the gauge.
+const MAX_RPM: u32 = 99999;
+/// Speed between each tick on the gauge.
+const TICK_SPEED: u32 = 20;
+/// Direction of the needle at 0, in degrees as [polar] measures them.
+const GAUGE_START: i32 = 225;
+/// Degrees the needle turns clockwise between 0 and [MAX_SPEED].
+const GAUGE_SWEEP: i32 = 270;
+const GAUGE_X: isize = 31;
+const GAUGE_Y: isize = 33;
+const GAUGE_RADIUS: isize = 29;
+const FUEL_BAR: Rect = Rect::new(86, 56, 40, 7);
+
+/// Vehicle state at one point in time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+struct Sample {
+    time_ms: u64,
+    speed: u32,
+    rpm: u32,
+    /// Percentage of a full tank.
+    fuel: u32,
+}
+
+/// Parses one sample per line, in time order, skipping blank lines and comments.
+fn parse_samples(text: &str) -> Result<Vec<Sample>, String> {
+    let mut samples: Vec<Sample> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 4 {
+            return Err(format!(
+                "line {}: expected 4 fields, found {}",
+                i + 1,
+                fields.len()
+            ));
+        }
+        let seconds: f64 = fields[0]
+            .parse()
+            .ok()
+            .filter(|seconds: &f64| seconds.is_finite() && *seconds >= 0.0)
+            .ok_or_else(|| format!("line {}: invalid time {:?}", i + 1, fields[0]))?;
+        let mut values = [0u32; 3];
+        for (value, field) in values.iter_mut().zip(&fields[1..]) {
+            *value = field
+                .parse()
+                .map_err(|_| format!("line {}: invalid value {:?}", i + 1, field))?;
+        }
+        let sample = Sample {
+            time_ms: (seconds * 1000.0).round() as u64,
+            speed: values[0],
+            rpm: values[1],
+            fuel: values[2].min(100),
+        };
+        if samples
+            .last()
+            .is_some_and(|last| last.time_ms > sample.time_ms)
+        {
+            return Err(format!("line {}: samples must be in time order", i + 1));
+        }
+        samples.push(sample);
+    }
+    if samples.is_empty() {
+        return Err("no samples".to_string());
+    }
+    Ok(samples)
+}
+
+/// Gets the state at `time_ms`, interpolating between the samples either side of it. Times before the first sample or
+/// after the last get the nearest one.
+fn sample_at(samples: &[Sample], time_ms: u64) -> Sample {
+    let next = samples.partition_point(|sample| sample.time_ms <= time_ms);
+    if next == 0 {
+        return samples[0];
+    }
+    let before = samples[next - 1];
+    let Some(&after) = samples.get(next) else {
+        return before;
+    };
+    let span = after.time_ms - before.time_ms;
+    let elapsed = time_ms - before.time_ms;
+    let lerp = |from: u32, to: u32| -> u32 {
+        let from = from as i64;
+        let to = to as i64;
+        (from + (to - from) * elapsed as i64 / span as i64) as u32
+    };
+    Sample {
+        time_ms,
+        speed: lerp(before.speed, after.speed),
+        rpm: lerp(before.rpm, after.rpm),
+        fuel: lerp(before.fuel, after.fuel),
+    }
+}
+
+/// Draws the dashboard for `sample` into a blank frame: a needle gauge on the left, with the speed as digits, the
+/// engine speed and a fuel bar on the right.
+fn draw_dashboard(
+    display: &mut DisplayArr,
+    readout: &mut SegmentReadout<3>,
+    sample: &Sample,
+) -> Result<(), DisplayArrErr> {
+    draw_arc(
+        display,
+        GAUGE_X,
+        GAUGE_Y,
+        GAUGE_RADIUS,
+        GAUGE_START,
+        -GAUGE_SWEEP,
+        DrawMode::Set,
+    );
+    for tick in (0..=MAX_SPEED).step_by(TICK_SPEED as usize) {
+        let degrees = needle_degrees(tick);
+        let (x0, y0) = polar(GAUGE_X, GAUGE_Y, GAUGE_RADIUS - 4, degrees);
+        let (x1, y1) = polar(GAUGE_X, GAUGE_Y, GAUGE_RADIUS, degrees);
+        draw_line(display, x0, y0, x1, y1, DrawMode::Set);
+    }
+    let (tip_x, tip_y) = polar(
+        GAUGE_X,
+        GAUGE_Y,
+        GAUGE_RADIUS - 6,
+        needle_degrees(sample.speed),
+    );
+    draw_line(display, GAUGE_X, GAUGE_Y, tip_x, tip_y, DrawMode::Set);
+    fill_circle(display, GAUGE_X, GAUGE_Y, 2, DrawMode::Set);
+
+    // The frame starts blank, so every digit has to be drawn again.
+    readout.invalidate();
+    readout.update(display, sample.speed)?;
+    draw_text(display, b"MPH", 110, 37)?;
+    let rpm = sample.rpm.min(MAX_RPM);
+    draw_text(display, format!("RPM {}", rpm).as_bytes(), 66, 47)?;
+
+    draw_text(display, b"FUEL", 66, 57)?;
+    draw_rect(display, &FUEL_BAR, DrawMode::Set)?;
+    let level = (FUEL_BAR.width - 2) * sample.fuel as usize / 100;
+    if level > 0 {
+        let inside = Rect::new(FUEL_BAR.x + 1, FUEL_BAR.y + 1, level, FUEL_BAR.height - 2);
+        fill_rect(display, &inside, DrawMode::Set)?;
+    }
+    Ok(())
+}
+
+/// Direction of the needle at `speed`.
+fn needle_degrees(speed: u32) -> i32 {
+    GAUGE_START - (speed.min(MAX_SPEED) as i32 * GAUGE_SWEEP / MAX_SPEED as i32)
+}
+
+struct Options {
+    fps: u32,
+    rate: f64,
+    looping: bool,
+    braille: bool,
+    frames: Option<u64>,
+    file: Option<String>,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        fps: 20,
+        rate: 1.0,
+        looping: false,
+        braille: false,
+        frames: None,
+        file: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--fps" => {
+                options.fps = value("--fps")?
+                    .parse()
+                    .ok()
+                    .filter(|&fps| fps > 0)
+                    .ok_or("--fps must be a whole number above 0")?;
+            }
+            "--rate" => {
+                options.rate = value("--rate")?
+                    .parse()
+                    .ok()
+                    .filter(|rate: &f64| rate.is_finite() && *rate > 0.0)
+                    .ok_or("--rate must be a number above 0")?;
+            }
+            "--frames" => {
+                options.frames = Some(
+                    value("--frames")?
+                        .parse()
+                        .map_err(|_| "--frames must be a whole number")?,
+                );
+            }
+            "--loop" => options.looping = true,
+            "--braille" => options.braille = true,
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ if options.file.is_none() => options.file = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn read_script(file: Option<&str>) -> Result<String, String> {
+    match file {
+        None => Ok(DEMO_SCRIPT.to_string()),
+        Some("-") => std::io::read_to_string(std::io::stdin()).map_err(|e| format!("stdin: {}", e)),
+        Some(path) => std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e)),
+    }
+}
+
+/// Plays `script` into `out`, which should be a terminal.
+fn run(options: &Options, script: &str, out: &mut impl Write) -> Result<(), String> {
+    let samples = parse_samples(script)?;
+    let duration_ms = samples[samples.len() - 1].time_ms;
+
+    let mut chain = SwapChain::<2>::new(SwapMode::Clear);
+    let mut readout = SegmentReadout::<3>::new(SegmentFont::SMALL, 74, 2, 2);
+    // Clear the screen once, then draw each frame over the last from the top left corner.
+    write!(out, "\x1b[2J").map_err(|e| e.to_string())?;
+    // Terminal row of the status line, just below the frame. Frames are all the same height, so this is set once the
+    // first one is drawn.
+    let mut status_row: usize = 1;
+
+    let frame_time = Duration::from_secs(1) / options.fps;
+    let start = Instant::now();
+    let mut frame: u64 = 0;
+    loop {
+        if options.frames.is_some_and(|frames| frame >= frames) {
+            break;
+        }
+        let played_ms = (frame as f64 * 1000.0 * options.rate / options.fps as f64) as u64;
+        if played_ms > duration_ms && !options.looping {
+            break;
+        }
+        let sample_ms = if options.looping {
+            played_ms.checked_rem(duration_ms).unwrap_or(0)
+        } else {
+            played_ms
+        };
+        let sample = sample_at(&samples, sample_ms);
+
+        draw_dashboard(chain.back(), &mut readout, &sample).map_err(|e| e.to_string())?;
+        let shown = *chain.front();
+        let changed = !FrameDiff::new(&shown, chain.back()).is_clean();
+        chain.present();
+        // Frames which look the same as the last one are not drawn again.
+        if changed || frame == 0 {
+            let display = chain.front();
+            let output = if options.braille {
+                format!("{:#}", Braille(display))
+            } else {
+                format!("{:#}", HalfBlocks(display))
+            };
+            write!(out, "\x1b[H{}", output).map_err(|e| e.to_string())?;
+            status_row = output.lines().count() + 1;
+        }
+        // The cursor is only left below the frame when it was drawn, so the status line is always placed explicitly.
+        writeln!(
+            out,
+            "\x1b[{};1H{:>7.1}s  {:>3} mph  {:>5} rpm  {:>3}% fuel\x1b[K",
+            status_row,
+            played_ms as f64 / 1000.0,
+            sample.speed,
+            sample.rpm,
+            sample.fuel
+        )
+        .map_err(|e| e.to_string())?;
+        out.flush().map_err(|e| e.to_string())?;
+
+        frame += 1;
+        if options.frames.is_none() {
+            let due = frame_time * frame as u32;
+            if let Some(wait) = due.checked_sub(start.elapsed()) {
+                std::thread::sleep(wait);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("speedometer-sim: {}", e);
+            }
+            eprintln!("{}", USAGE);
+            return if e.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(2)
+            };
+        }
+    };
+    let result = read_script(options.file.as_deref())
+        .and_then(|script| run(&options, &script, &mut std::io::stdout().lock()));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("speedometer-sim: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples() {
+        let samples =
+            parse_samples("# a comment\n0 0 800 50\n\n1.5 30 2300 49 # cruising\n").unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(
+            samples[1],
+            Sample {
+                time_ms: 1500,
+                speed: 30,
+                rpm: 2300,
+                fuel: 49
+            }
+        );
+        assert_eq!(sample_at(&samples, 750).speed, 15);
+        assert_eq!(sample_at(&samples, 750).rpm, 1550);
+        assert_eq!(sample_at(&samples, 5000), samples[1]);
+        assert!(parse_samples(DEMO_SCRIPT).is_ok());
+
+        assert!(parse_samples("").is_err());
+        assert!(parse_samples("0 0 800").is_err());
+        assert!(parse_samples("0 fast 800 50").is_err());
+        assert!(parse_samples("2 0 800 50\n1 0 800 50").is_err());
+    }
+
+    #[test]
+    fn dashboard() {
+        let mut readout = SegmentReadout::<3>::new(SegmentFont::SMALL, 74, 2, 2);
+        for (speed, rpm) in [(0, 0), (88, 3200), (MAX_SPEED, MAX_RPM), (1000, u32::MAX)] {
+            let mut display = DisplayArr::new();
+            let sample = Sample {
+                time_ms: 0,
+                speed,
+                rpm,
+                fuel: 100,
+            };
+            draw_dashboard(&mut display, &mut readout, &sample).unwrap();
+            // The needle pins at either end of the gauge.
+            let (x, y) = polar(GAUGE_X, GAUGE_Y, GAUGE_RADIUS - 6, needle_degrees(speed));
+            assert!(
+                display.bit(y as usize, x as usize).unwrap(),
+                "err speed {:}",
+                speed
+            );
+        }
+        assert_eq!(needle_degrees(MAX_SPEED), GAUGE_START - GAUGE_SWEEP);
+        assert_eq!(needle_degrees(1000), GAUGE_START - GAUGE_SWEEP);
+    }
+
+    #[test]
+    fn unchanged_frames() {
+        let options = parse_options(["--frames", "4"].map(String::from).into_iter()).unwrap();
+        let mut out = Vec::new();
+        run(&options, "0 0 800 50\n10 0 800 50\n", &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        // The frame is drawn once, and every status line goes on the row below it instead of piling up.
+        assert_eq!(out.matches("\x1b[H").count(), 1);
+        assert_eq!(out.matches(" mph ").count(), 4);
+        let status = format!("\x1b[{};1H", 32 + 2 + 1);
+        assert_eq!(out.matches(&status).count(), 4);
+    }
+}
@@ -0,0 +1,479 @@
+use core::fmt::Formatter;
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+use core::write;
+
+use crate::newspeed::{DisplayArr, Framebuffer};
+
+/// Most bytes a single literal or run can hold.
+const MAX_PACKET: usize = 128;
+/// Bytes in an uncompressed [DisplayArr]. See [frame_len].
+pub const FRAME_BYTES: usize = frame_len(DisplayArr::WIDTH, DisplayArr::LEN);
+/// Most bytes a [DisplayArr] compresses to. See [max_compressed_len].
+pub const MAX_COMPRESSED_LEN: usize = max_compressed_len(DisplayArr::WIDTH, DisplayArr::LEN);
+
+/// Gets the number of bytes in an uncompressed `width` x `height` frame: each row from the top, in bytes like
+/// [Framebuffer::row_byte], so column 0 is the highest bit of the first byte and the last byte is padded with clear
+/// columns.
+pub const fn frame_len(width: usize, height: usize) -> usize {
+    width.div_ceil(8) * height
+}
+
+/// Gets the most bytes a `width` x `height` frame compresses to, when nothing repeats and every byte is a literal.
+pub const fn max_compressed_len(width: usize, height: usize) -> usize {
+    let frame = frame_len(width, height);
+    frame + frame.div_ceil(MAX_PACKET)
+}
+
+/// Error variants for compressing or decompressing a frame.
+pub enum CompressError {
+    /// The output buffer is full. Argument is the length of the buffer.
+    BufferFull(usize),
+    /// The data ended before the frame was complete. Argument is the number of frame bytes decoded.
+    Truncated(usize),
+    /// A run or literal goes past the end of the frame. Argument is the byte offset of its header.
+    Malformed(usize),
+}
+
+impl core::fmt::Display for CompressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CompressError::BufferFull(length) => {
+                write!(f, "Compressed frame does not fit in {:} bytes", length)
+            }
+            CompressError::Truncated(decoded) => write!(
+                f,
+                "Compressed frame ended early: decoded only {:} bytes",
+                decoded
+            ),
+            CompressError::Malformed(position) => write!(
+                f,
+                "Compressed frame is malformed at {:}: data goes past the end of the frame",
+                position
+            ),
+        }
+    }
+}
+
+impl core::fmt::Debug for CompressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Gets byte `idx` of `frame` in the order it is compressed, as described by [frame_len].
+fn frame_byte<const W: usize, const H: usize, const S: usize>(
+    frame: &Framebuffer<W, H, S>,
+    idx: usize,
+) -> u8 {
+    let row_bytes = W.div_ceil(8);
+    // Bytes within the frame are always in bounds.
+    frame
+        .row_byte(idx / row_bytes, idx % row_bytes)
+        .unwrap_or(0)
+}
+
+/// Gets how many times byte `start` of `frame` repeats, up to [MAX_PACKET].
+fn run_length<const W: usize, const H: usize, const S: usize>(
+    frame: &Framebuffer<W, H, S>,
+    start: usize,
+) -> usize {
+    let byte = frame_byte(frame, start);
+    (start..frame_len(W, H))
+        .take(MAX_PACKET)
+        .take_while(|&i| frame_byte(frame, i) == byte)
+        .count()
+}
+
+/// Compresses `frame` into `out` with run-length coding, returning the number of bytes written. Returns
+/// [CompressError::BufferFull] if `out` is too short, which it never is if it holds [MAX_COMPRESSED_LEN] bytes.
+///
+/// The coding is PackBits: a header byte `n` from 0 through 127 is followed by `n + 1` literal bytes, and a header
+/// from 129 through 255 is followed by one byte to repeat `257 - n` times. A header of 128 is skipped.
+pub fn compress<const W: usize, const H: usize, const S: usize>(
+    frame: &Framebuffer<W, H, S>,
+    out: &mut [u8],
+) -> Result<usize, CompressError> {
+    let length = frame_len(W, H);
+    let mut written: usize = 0;
+    let mut write = |data: &[u8]| {
+        let end = written + data.len();
+        if end > out.len() {
+            return Err(CompressError::BufferFull(out.len()));
+        }
+        out[written..end].copy_from_slice(data);
+        written = end;
+        Ok(())
+    };
+
+    let mut i: usize = 0;
+    while i < length {
+        let run = run_length(frame, i);
+        if run >= 2 {
+            write(&[(257 - run) as u8, frame_byte(frame, i)])?;
+            i += run;
+            continue;
+        }
+        // Pairs are cheaper to leave in a literal than to split it around.
+        let start = i;
+        i += 1;
+        while i < length && i - start < MAX_PACKET && run_length(frame, i) < 3 {
+            i += 1;
+        }
+        write(&[(i - start - 1) as u8])?;
+        for k in start..i {
+            write(&[frame_byte(frame, k)])?;
+        }
+    }
+    Ok(written)
+}
+
+/// Compresses the pixels which changed from `previous` to `frame`, like [compress]. Consecutive frames of a recording
+/// are mostly the same, so their xor is mostly blank and compresses much better than either frame.
+pub fn compress_delta<const W: usize, const H: usize, const S: usize>(
+    previous: &Framebuffer<W, H, S>,
+    frame: &Framebuffer<W, H, S>,
+    out: &mut [u8],
+) -> Result<usize, CompressError> {
+    compress(&(*previous ^ *frame), out)
+}
+
+/// What a [FrameDecoder] expects next.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Packet {
+    Header,
+    /// This many literal bytes.
+    Literal(usize),
+    /// A byte to repeat this many times.
+    Run(usize),
+}
+
+/// Decompresses a frame made by [compress] or [compress_delta] straight into a [Framebuffer] as its data arrives,
+/// writing each row as soon as it is complete. Only the row being decoded is buffered.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FrameDecoder<const W: usize, const H: usize, const S: usize> {
+    /// Whether to xor decoded rows into the display, to apply a delta, or replace them.
+    delta: bool,
+    packet: Packet,
+    /// Frame bytes decoded so far.
+    decoded: usize,
+    /// Data bytes read so far.
+    read: usize,
+    /// The row being decoded, in the same form as [Framebuffer::row_words].
+    row: [u64; S],
+}
+
+impl<const W: usize, const H: usize, const S: usize> FrameDecoder<W, H, S> {
+    /// Creates a decoder for a frame made by [compress], which replaces the contents of the display.
+    pub const fn new() -> Self {
+        Self {
+            delta: false,
+            packet: Packet::Header,
+            decoded: 0,
+            read: 0,
+            row: [0; S],
+        }
+    }
+
+    /// Creates a decoder for a frame made by [compress_delta], which expects the display to hold the previous frame.
+    pub const fn new_delta() -> Self {
+        Self {
+            delta: true,
+            ..Self::new()
+        }
+    }
+
+    /// Whether the whole frame has been decoded.
+    pub const fn is_done(&self) -> bool {
+        self.decoded == frame_len(W, H)
+    }
+
+    /// Adds a decoded byte to the current row, writing the row into `display` once it is complete.
+    const fn push(&mut self, display: &mut Framebuffer<W, H, S>, byte: u8) {
+        let row_bytes = W.div_ceil(8);
+        let idx = self.decoded % row_bytes;
+        self.row[idx / 8] |= (byte as u64) << (56 - 8 * (idx % 8));
+        self.decoded += 1;
+        if self.decoded.is_multiple_of(row_bytes) {
+            let y = self.decoded / row_bytes - 1;
+            // Rows below H are always in bounds.
+            if let (true, Ok(previous)) = (self.delta, display.row_words(y)) {
+                let mut i: usize = 0;
+                while i < S {
+                    self.row[i] ^= previous[i];
+                    i += 1;
+                }
+            }
+            let _ = display.set_row_words(y, self.row);
+            self.row = [0; S];
+        }
+    }
+
+    /// Decodes as much of `data` into `display` as belongs to the frame, returning the number of bytes used. Once the
+    /// frame is done, the rest of `data` is left alone, so frames can be stored back to back. Returns
+    /// [CompressError::Malformed] if a packet goes past the end of the frame.
+    pub const fn feed(
+        &mut self,
+        display: &mut Framebuffer<W, H, S>,
+        data: &[u8],
+    ) -> Result<usize, CompressError> {
+        let mut i: usize = 0;
+        while i < data.len() && !self.is_done() {
+            let byte = data[i];
+            match self.packet {
+                Packet::Header => {
+                    let length = match byte {
+                        0..=127 => byte as usize + 1,
+                        128 => 0,
+                        _ => 257 - byte as usize,
+                    };
+                    if self.decoded + length > frame_len(W, H) {
+                        return Err(CompressError::Malformed(self.read + i));
+                    }
+                    self.packet = match byte {
+                        0..=127 => Packet::Literal(length),
+                        128 => Packet::Header,
+                        _ => Packet::Run(length),
+                    };
+                }
+                Packet::Literal(remaining) => {
+                    self.push(display, byte);
+                    self.packet = if remaining > 1 {
+                        Packet::Literal(remaining - 1)
+                    } else {
+                        Packet::Header
+                    };
+                }
+                Packet::Run(count) => {
+                    let mut k: usize = 0;
+                    while k < count {
+                        self.push(display, byte);
+                        k += 1;
+                    }
+                    self.packet = Packet::Header;
+                }
+            }
+            i += 1;
+        }
+        self.read += i;
+        Ok(i)
+    }
+
+    /// Checks that the whole frame has been decoded. Returns [CompressError::Truncated] if not.
+    pub const fn finish(&self) -> Result<(), CompressError> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(CompressError::Truncated(self.decoded))
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, const S: usize> Default for FrameDecoder<W, H, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decompresses a frame made by [compress]. Bytes after the frame are ignored. Meant for `const` splash screens from
+/// `include_bytes!`, where data which does not decompress fails compilation.
+pub const fn decompress<const W: usize, const H: usize, const S: usize>(
+    data: &[u8],
+) -> Result<Framebuffer<W, H, S>, CompressError> {
+    let mut output = Framebuffer::new();
+    let mut decoder = FrameDecoder::new();
+    if let Err(e) = decoder.feed(&mut output, data) {
+        return Err(e);
+    }
+    match decoder.finish() {
+        Ok(()) => Ok(output),
+        Err(e) => Err(e),
+    }
+}
+
+/// Applies a frame made by [compress_delta] to `display`, which must hold the frame it was made against. If the data
+/// is invalid, `display` may be partly updated.
+pub const fn decompress_delta<const W: usize, const H: usize, const S: usize>(
+    display: &mut Framebuffer<W, H, S>,
+    data: &[u8],
+) -> Result<(), CompressError> {
+    let mut decoder = FrameDecoder::new_delta();
+    if let Err(e) = decoder.feed(display, data) {
+        return Err(e);
+    }
+    decoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::{draw_circle, DrawMode};
+    use crate::newspeed::strips;
+    use crate::text::draw_text;
+
+    fn dashboard(speed: &[u8]) -> DisplayArr {
+        let mut display = DisplayArr::new();
+        draw_text(&mut display, b"SPEED", 2, 2).unwrap();
+        draw_text(&mut display, speed, 40, 30).unwrap();
+        draw_circle(&mut display, 96, 32, 24, DrawMode::Set);
+        display
+    }
+
+    /// Decompresses a [DisplayArr], for checking errors.
+    fn decompress_display(data: &[u8]) -> Result<DisplayArr, CompressError> {
+        decompress(data)
+    }
+
+    fn round_trip(frame: &DisplayArr) -> usize {
+        let mut out = [0u8; MAX_COMPRESSED_LEN];
+        let length = compress(frame, &mut out).unwrap();
+        assert_eq!(&decompress(&out[..length]).unwrap(), frame);
+        length
+    }
+
+    #[test]
+    fn run_length() {
+        // Blank and full frames are 8 runs of 128 bytes each.
+        assert_eq!(round_trip(&DisplayArr::new()), 16);
+        assert_eq!(round_trip(&DisplayArr::new_full()), 16);
+        let mut out = [0u8; MAX_COMPRESSED_LEN];
+        compress(&DisplayArr::new_full(), &mut out).unwrap();
+        assert_eq!(out[..2], [0x81, 0xff]);
+
+        // Text and outlines compress to well under half a frame.
+        assert!(round_trip(&dashboard(b"88")) < FRAME_BYTES / 2);
+        let noise = DisplayArr::splat(0x0123456789abcdef, 0xfedcba9876543210);
+        assert!(round_trip(&noise) <= MAX_COMPRESSED_LEN);
+
+        // A literal, then a run.
+        let mut frame = DisplayArr::new();
+        frame.set_row(0, 0x0102u128 << 112).unwrap();
+        let length = compress(&frame, &mut out).unwrap();
+        assert_eq!(out[..5], [0x01, 0x01, 0x02, 0x81, 0x00]);
+        assert_eq!(decompress(&out[..length]).unwrap(), frame);
+    }
+
+    #[test]
+    fn deltas() {
+        let frames = [dashboard(b"86"), dashboard(b"87"), dashboard(b"88")];
+        let mut stream = [0u8; 3 * MAX_COMPRESSED_LEN];
+        let mut length = compress(&frames[0], &mut stream).unwrap();
+        for pair in frames.windows(2) {
+            let delta = compress_delta(&pair[0], &pair[1], &mut stream[length..]).unwrap();
+            assert!(delta < 100);
+            length += delta;
+        }
+
+        // Frames stored back to back decode one after another.
+        let mut display = DisplayArr::new_full();
+        let mut used = 0;
+        for (i, frame) in frames.iter().enumerate() {
+            let mut decoder = if i == 0 {
+                FrameDecoder::new()
+            } else {
+                FrameDecoder::new_delta()
+            };
+            used += decoder.feed(&mut display, &stream[used..length]).unwrap();
+            decoder.finish().unwrap();
+            assert_eq!(&display, frame);
+        }
+        assert_eq!(used, length);
+
+        let mut display = frames[0];
+        let delta = compress_delta(&frames[0], &frames[2], &mut stream).unwrap();
+        decompress_delta(&mut display, &stream[..delta]).unwrap();
+        assert_eq!(display, frames[2]);
+    }
+
+    #[test]
+    fn streaming() {
+        let frame = dashboard(b"42");
+        let mut out = [0u8; MAX_COMPRESSED_LEN];
+        let length = compress(&frame, &mut out).unwrap();
+        for chunk_size in [1, 2, 7, 64] {
+            let mut display = DisplayArr::new_full();
+            let mut decoder = FrameDecoder::new();
+            for chunk in out[..length].chunks(chunk_size) {
+                assert!(!decoder.is_done());
+                assert_eq!(decoder.feed(&mut display, chunk).unwrap(), chunk.len());
+            }
+            assert!(decoder.is_done());
+            assert_eq!(display, frame);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut out = [0u8; 15];
+        assert!(matches!(
+            compress(&DisplayArr::new(), &mut out),
+            Err(CompressError::BufferFull(15))
+        ));
+        assert!(matches!(
+            decompress_display(&[0x81, 0x00]),
+            Err(CompressError::Truncated(128))
+        ));
+        assert!(matches!(
+            decompress_display(&[0x01, 0xff]),
+            Err(CompressError::Truncated(1))
+        ));
+        // Seven runs of 128 and a literal leave 127 bytes, not enough for another run of 128.
+        let mut overrun = [0x81, 0x00].repeat(7);
+        overrun.extend_from_slice(&[0x00, 0x00, 0x81, 0x00]);
+        assert!(matches!(
+            decompress_display(&overrun),
+            Err(CompressError::Malformed(16))
+        ));
+    }
+
+    #[test]
+    fn other_sizes() {
+        // Rows of a 20 column framebuffer take 3 bytes, with the last 4 columns as clear padding: a run of two 0xff,
+        // then a literal of the rest, starting with the padded 0xf0.
+        type Narrow = Framebuffer<20, 4, { strips(20) }>;
+        assert_eq!(frame_len(20, 4), 12);
+        let mut narrow = Narrow::new_full();
+        narrow.clear_bit(1, 19).unwrap();
+        let mut out = [0u8; max_compressed_len(20, 4)];
+        let length = compress(&narrow, &mut out).unwrap();
+        assert_eq!(out[..6], [0xff, 0xff, 0x09, 0xf0, 0xff, 0xff]);
+        assert_eq!(decompress::<20, 4, 1>(&out[..length]).unwrap(), narrow);
+
+        // Wide framebuffers stream through a decoder a row of four words at a time.
+        type Wide = Framebuffer<256, 64, { strips(256) }>;
+        let mut previous = Wide::new();
+        previous.set_row_words(0, [1, 2, 3, 4]).unwrap();
+        let mut frame = previous;
+        frame.set_bit(63, 255).unwrap();
+        let mut out = [0u8; max_compressed_len(256, 64)];
+        let length = compress(&frame, &mut out).unwrap();
+        assert_eq!(decompress::<256, 64, 4>(&out[..length]).unwrap(), frame);
+        let length = compress_delta(&previous, &frame, &mut out).unwrap();
+        let mut display = previous;
+        let mut decoder = FrameDecoder::new_delta();
+        for chunk in out[..length].chunks(3) {
+            decoder.feed(&mut display, chunk).unwrap();
+        }
+        decoder.finish().unwrap();
+        assert_eq!(display, frame);
+        assert!(matches!(
+            decompress::<256, 64, 4>(&out[..length - 1]),
+            Err(CompressError::Truncated(_))
+        ));
+    }
+
+    #[test]
+    fn const_splash() {
+        const DATA: [u8; 18] = [
+            0x81, 0xff, 0x80, 0x81, 0x00, 0x81, 0x00, 0x81, 0x00, 0x81, 0x00, 0x81, 0x00, 0x81,
+            0x00, 0x81, 0x00, 0x00,
+        ];
+        const SPLASH: DisplayArr = match decompress(&DATA) {
+            Ok(frame) => frame,
+            Err(_) => panic!(),
+        };
+        assert_eq!(SPLASH.row(7).unwrap(), u128::MAX);
+        assert_eq!(SPLASH.row(8).unwrap(), 0);
+    }
+}
@@ -0,0 +1,171 @@
+use core::iter::Iterator;
+use core::option::Option;
+use core::option::Option::None;
+use core::option::Option::Some;
+
+use crate::newspeed::{Framebuffer, Rect};
+use crate::ssd1306::PAGE_HEIGHT;
+
+/// The pixels which changed between two frames, found by xor-ing them together. A driver can send only the rows,
+/// pages or rectangles which are dirty instead of the whole frame.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FrameDiff<const W: usize, const H: usize, const S: usize> {
+    changed: Framebuffer<W, H, S>,
+    rows: u64,
+}
+
+impl<const W: usize, const H: usize, const S: usize> FrameDiff<W, H, S> {
+    /// Compares `current` against `previous`, the frame the display is already showing.
+    pub fn new(previous: &Framebuffer<W, H, S>, current: &Framebuffer<W, H, S>) -> Self {
+        let changed = *previous ^ *current;
+        Self {
+            rows: changed.occupied_rows(),
+            changed,
+        }
+    }
+
+    /// Gets the pixels which differ between the two frames.
+    pub fn changed(&self) -> &Framebuffer<W, H, S> {
+        &self.changed
+    }
+
+    /// Whether the two frames are the same.
+    pub fn is_clean(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Gets which rows changed, in the same form as [Framebuffer::column].
+    pub fn row_mask(&self) -> u64 {
+        self.rows
+    }
+
+    /// Gets the index of every row which changed, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = usize> {
+        let mut rows = self.rows;
+        core::iter::from_fn(move || {
+            if rows == 0 {
+                return None;
+            }
+            let row = rows.leading_zeros() as usize;
+            rows &= !(1 << (63 - row));
+            Some(row)
+        })
+    }
+
+    /// Gets the smallest rectangle holding every changed pixel in `rows`, which must not be empty.
+    fn bounds_of(&self, rows: u64) -> Rect {
+        let words = self.changed.union_rows(rows);
+        let first = words.iter().position(|&word| word != 0).unwrap_or(0);
+        let last = words.iter().rposition(|&word| word != 0).unwrap_or(0);
+        let left = first * 64 + words[first].leading_zeros() as usize;
+        let right = (last + 1) * 64 - words[last].trailing_zeros() as usize;
+        let top = rows.leading_zeros() as usize;
+        let bottom = 64 - rows.trailing_zeros() as usize;
+        Rect::new(left, top, right - left, bottom - top)
+    }
+
+    /// Gets the smallest rectangle holding every changed pixel, or [None] if nothing changed.
+    pub fn bounds(&self) -> Option<Rect> {
+        if self.is_clean() {
+            None
+        } else {
+            Some(self.bounds_of(self.rows))
+        }
+    }
+
+    /// Gets a rectangle for each run of consecutive changed rows, spanning the columns which changed within it. Runs
+    /// are separated by at least one unchanged row, so the rectangles never overlap.
+    pub fn rects(&self) -> impl Iterator<Item = Rect> + '_ {
+        let mut rows = self.rows;
+        core::iter::from_fn(move || {
+            if rows == 0 {
+                return None;
+            }
+            let top = rows.leading_zeros();
+            let run = (rows << top).leading_ones();
+            let band = (u64::MAX >> (64 - run)) << (64 - top - run);
+            rows &= !band;
+            Some(self.bounds_of(band))
+        })
+    }
+
+    /// Gets a rectangle for each 8 row page which changed, as used by page ordered controllers like the SSD1306. Each
+    /// rectangle covers the whole page, and the columns which changed within it.
+    pub fn pages(&self) -> impl Iterator<Item = Rect> + '_ {
+        (0..H.div_ceil(PAGE_HEIGHT)).filter_map(move |page| {
+            let band = self.rows & ((u64::MAX << 56) >> (page * PAGE_HEIGHT));
+            if band == 0 {
+                return None;
+            }
+            let columns = self.bounds_of(band);
+            let top = page * PAGE_HEIGHT;
+            let bottom = if top + PAGE_HEIGHT < H {
+                top + PAGE_HEIGHT
+            } else {
+                H
+            };
+            Some(Rect::new(columns.x, top, columns.width, bottom - top))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newspeed::DisplayArr;
+
+    #[test]
+    fn clean_frames() {
+        let frame = DisplayArr::splat(0x0123456789abcdef, 0xfedcba9876543210);
+        let diff = FrameDiff::new(&frame, &frame);
+        assert!(diff.is_clean());
+        assert_eq!(diff.row_mask(), 0);
+        assert!(diff.bounds().is_none());
+        assert_eq!(diff.rows().count(), 0);
+        assert_eq!(diff.rects().count(), 0);
+        assert_eq!(diff.pages().count(), 0);
+    }
+
+    #[test]
+    fn dirty_regions() {
+        let previous = DisplayArr::new();
+        let mut current = previous;
+        current.set_bit(2, 10).unwrap();
+        current.set_bit(3, 70).unwrap();
+        current.set_bit(20, 127).unwrap();
+        current.set_bit(63, 0).unwrap();
+        let diff = FrameDiff::new(&previous, &current);
+
+        assert!(!diff.is_clean());
+        assert_eq!(diff.changed(), &current);
+        assert_eq!(diff.row_mask(), (0b11 << 60) | (1 << 43) | 1);
+        assert!(diff.rows().eq([2, 3, 20, 63]));
+        assert_eq!(diff.bounds(), Some(Rect::new(0, 2, 128, 62)));
+        assert!(diff.rects().eq([
+            Rect::new(10, 2, 61, 2),
+            Rect::new(127, 20, 1, 1),
+            Rect::new(0, 63, 1, 1),
+        ]));
+        assert!(diff.pages().eq([
+            Rect::new(10, 0, 61, 8),
+            Rect::new(127, 16, 1, 8),
+            Rect::new(0, 56, 1, 8),
+        ]));
+
+        // Pixels turning off are as dirty as pixels turning on.
+        let back = FrameDiff::new(&current, &previous);
+        assert!(back.rects().eq(diff.rects()));
+    }
+
+    #[test]
+    fn other_sizes() {
+        let previous = Framebuffer::<200, 20, 4>::new();
+        let mut current = previous;
+        current.set_bit(19, 150).unwrap();
+        current.set_bit(18, 199).unwrap();
+        let diff = FrameDiff::new(&previous, &current);
+        assert_eq!(diff.bounds(), Some(Rect::new(150, 18, 50, 2)));
+        // The last page is cut short by the bottom of the framebuffer.
+        assert!(diff.pages().eq([Rect::new(150, 16, 50, 4)]));
+    }
+}
@@ -0,0 +1,661 @@
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+
+use crate::newspeed::{DisplayArr, DisplayArrErr, Rect};
+
+/// How a primitive combines its pixels with the pixels already in a [DisplayArr].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DrawMode {
+    /// Turns the pixels on.
+    Set,
+    /// Turns the pixels off.
+    Clear,
+    /// Inverts the pixels, so drawing the same shape twice restores the display.
+    Xor,
+}
+
+impl DrawMode {
+    /// Combines `row` with the pixels set in `mask`.
+    pub const fn apply(self, row: u128, mask: u128) -> u128 {
+        match self {
+            DrawMode::Set => row | mask,
+            DrawMode::Clear => row & !mask,
+            DrawMode::Xor => row ^ mask,
+        }
+    }
+}
+
+/// Draws the pixels set in `mask` into row `y` of `display` with `mode`. Returns [DisplayArrErr::InvalidRowError] if
+/// `y` is out of bounds.
+pub const fn apply_row(
+    display: &mut DisplayArr,
+    y: usize,
+    mask: u128,
+    mode: DrawMode,
+) -> Result<(), DisplayArrErr> {
+    let row = match display.row(y) {
+        Ok(row) => row,
+        Err(e) => return Err(e),
+    };
+    display.set_row(y, mode.apply(row, mask))
+}
+
+/// `sin` of 0 through 90 degrees, scaled by `1 << 14`.
+const SIN_TABLE: [i32; 91] = [
+    0, 286, 572, 857, 1143, 1428, 1713, 1997, 2280, 2563, 2845, 3126, 3406, 3686, 3964, 4240, 4516,
+    4790, 5063, 5334, 5604, 5872, 6138, 6402, 6664, 6924, 7182, 7438, 7692, 7943, 8192, 8438, 8682,
+    8923, 9162, 9397, 9630, 9860, 10087, 10311, 10531, 10749, 10963, 11174, 11381, 11585, 11786,
+    11982, 12176, 12365, 12551, 12733, 12911, 13085, 13255, 13421, 13583, 13741, 13894, 14044,
+    14189, 14330, 14466, 14598, 14726, 14849, 14968, 15082, 15191, 15296, 15396, 15491, 15582,
+    15668, 15749, 15826, 15897, 15964, 16026, 16083, 16135, 16182, 16225, 16262, 16294, 16322,
+    16344, 16362, 16374, 16382, 16384,
+];
+/// Fixed point scale of [sin] and [cos].
+const TRIG_SHIFT: u32 = 14;
+
+/// `sin` of `degrees`, scaled by `1 << 14`.
+pub const fn sin(degrees: i32) -> i32 {
+    let degrees = degrees.rem_euclid(360);
+    match degrees {
+        0..=90 => SIN_TABLE[degrees as usize],
+        91..=180 => SIN_TABLE[(180 - degrees) as usize],
+        181..=270 => -SIN_TABLE[(degrees - 180) as usize],
+        _ => -SIN_TABLE[(360 - degrees) as usize],
+    }
+}
+
+/// `cos` of `degrees`, scaled by `1 << 14`.
+pub const fn cos(degrees: i32) -> i32 {
+    sin(degrees + 90)
+}
+
+/// Gets the point `radius` pixels from (`cx`, `cy`) in the direction of `degrees`, such as the tip of a gauge needle.
+/// Angles are counter-clockwise from the positive x axis, so 90 degrees points up the display.
+pub const fn polar(cx: isize, cy: isize, radius: isize, degrees: i32) -> (isize, isize) {
+    const fn scale(radius: isize, value: i32) -> isize {
+        let product = radius as i64 * value as i64;
+        let half = 1i64 << (TRIG_SHIFT - 1);
+        let rounded = if product < 0 {
+            (product - half) / (1 << TRIG_SHIFT)
+        } else {
+            (product + half) / (1 << TRIG_SHIFT)
+        };
+        rounded as isize
+    }
+    (
+        cx + scale(radius, cos(degrees)),
+        cy - scale(radius, sin(degrees)),
+    )
+}
+
+/// Pixels of a shape, collected row by row and clipped to `clip`. Collecting before drawing means each pixel is drawn
+/// once however many times the shape covers it, which matters for [DrawMode::Xor].
+pub(crate) struct Shape {
+    rows: [u128; DisplayArr::LEN],
+    clip: Rect,
+}
+
+impl Shape {
+    /// Panics if `clip` does not lie within a [DisplayArr].
+    pub(crate) const fn new(clip: Rect) -> Self {
+        if DisplayArr::check_rect(&clip).is_err() {
+            panic!("shape clip rectangle does not fit in a DisplayArr");
+        }
+        Self {
+            rows: [0; DisplayArr::LEN],
+            clip,
+        }
+    }
+
+    /// Adds the pixel at column `x`, row `y`, if it lies within the clip rectangle.
+    pub(crate) const fn plot(&mut self, x: isize, y: isize) {
+        if x < self.clip.x as isize
+            || x >= self.clip.right() as isize
+            || y < self.clip.y as isize
+            || y >= self.clip.bottom() as isize
+        {
+            return;
+        }
+        self.rows[y as usize] |= 1u128 << (DisplayArr::WIDTH - 1 - x as usize);
+    }
+
+    /// Adds the pixels from column `x0` to column `x1` inclusive on row `y`, clipped to the clip rectangle.
+    pub(crate) const fn span(&mut self, x0: isize, x1: isize, y: isize) {
+        let (x0, x1) = if x0 <= x1 { (x0, x1) } else { (x1, x0) };
+        if y < self.clip.y as isize || y >= self.clip.bottom() as isize {
+            return;
+        }
+        let left = if x0 < self.clip.x as isize {
+            self.clip.x as isize
+        } else {
+            x0
+        };
+        let right = if x1 >= self.clip.right() as isize {
+            self.clip.right() as isize - 1
+        } else {
+            x1
+        };
+        if left > right {
+            return;
+        }
+        self.rows[y as usize] |= DisplayArr::row_mask(left as usize, (right - left + 1) as usize);
+    }
+
+    /// Draws the collected pixels into `display` with `mode`.
+    pub(crate) const fn draw(&self, display: &mut DisplayArr, mode: DrawMode) {
+        let mut y = self.clip.y;
+        while y < self.clip.bottom() {
+            if self.rows[y] != 0 {
+                // Rows within the clip rectangle are always valid.
+                let _ = apply_row(display, y, self.rows[y], mode);
+            }
+            y += 1;
+        }
+    }
+
+    /// Adds a line from (`x0`, `y0`) to (`x1`, `y1`), including both ends.
+    pub(crate) const fn line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let step_x = if x0 < x1 { 1 } else { -1 };
+        let step_y = if y0 < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        let (mut x, mut y) = (x0, y0);
+        loop {
+            self.plot(x, y);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Adds the outline of `rect`.
+    pub(crate) const fn rect(&mut self, rect: &Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        let (left, top) = (rect.x as isize, rect.y as isize);
+        let (right, bottom) = (rect.right() as isize - 1, rect.bottom() as isize - 1);
+        self.span(left, right, top);
+        self.span(left, right, bottom);
+        let mut y = top + 1;
+        while y < bottom {
+            self.plot(left, y);
+            self.plot(right, y);
+            y += 1;
+        }
+    }
+
+    /// Adds the points of an ellipse at each step of the midpoint algorithm. If `fill` is set, the spans between them
+    /// are added as well.
+    pub(crate) const fn ellipse(&mut self, cx: isize, cy: isize, rx: isize, ry: isize, fill: bool) {
+        if rx < 0 || ry < 0 {
+            return;
+        }
+        if ry == 0 {
+            self.span(cx - rx, cx + rx, cy);
+            return;
+        }
+        let (rx2, ry2) = (rx as i64 * rx as i64, ry as i64 * ry as i64);
+        let (mut x, mut y): (i64, i64) = (0, ry as i64);
+        let mut px: i64 = 0;
+        let mut py: i64 = 2 * rx2 * y;
+
+        // Region 1, where the slope is shallower than -1 and x steps every time.
+        let mut p = ry2 - rx2 * ry as i64 + rx2 / 4;
+        while px < py {
+            self.ellipse_points(cx, cy, x as isize, y as isize, fill);
+            x += 1;
+            px += 2 * ry2;
+            if p < 0 {
+                p += ry2 + px;
+            } else {
+                y -= 1;
+                py -= 2 * rx2;
+                p += ry2 + px - py;
+            }
+        }
+
+        // Region 2, where y steps every time.
+        p = (ry2 * (2 * x + 1) * (2 * x + 1)) / 4 + rx2 * (y - 1) * (y - 1) - rx2 * ry2;
+        while y >= 0 {
+            self.ellipse_points(cx, cy, x as isize, y as isize, fill);
+            y -= 1;
+            py -= 2 * rx2;
+            if p > 0 {
+                p += rx2 - py;
+            } else {
+                x += 1;
+                px += 2 * ry2;
+                p += rx2 - py + px;
+            }
+        }
+    }
+
+    const fn ellipse_points(&mut self, cx: isize, cy: isize, x: isize, y: isize, fill: bool) {
+        if fill {
+            self.span(cx - x, cx + x, cy - y);
+            self.span(cx - x, cx + x, cy + y);
+        } else {
+            self.plot(cx + x, cy + y);
+            self.plot(cx - x, cy + y);
+            self.plot(cx + x, cy - y);
+            self.plot(cx - x, cy - y);
+        }
+    }
+
+    /// Adds the points of a circle from the midpoint algorithm which lie within `sweep` degrees counter-clockwise of
+    /// `start`. If `fill` is set, the spans between them are added instead, and the angles are ignored.
+    pub(crate) const fn circle(
+        &mut self,
+        cx: isize,
+        cy: isize,
+        radius: isize,
+        start: i32,
+        sweep: i32,
+        fill: bool,
+    ) {
+        if radius < 0 {
+            return;
+        }
+        let (mut x, mut y) = (radius, 0isize);
+        let mut error = 1 - radius;
+        while x >= y {
+            if fill {
+                self.span(cx - x, cx + x, cy + y);
+                self.span(cx - x, cx + x, cy - y);
+                self.span(cx - y, cx + y, cy + x);
+                self.span(cx - y, cx + y, cy - x);
+            } else {
+                let points = [
+                    (x, y),
+                    (y, x),
+                    (-y, x),
+                    (-x, y),
+                    (-x, -y),
+                    (-y, -x),
+                    (y, -x),
+                    (x, -y),
+                ];
+                let mut i: usize = 0;
+                while i < points.len() {
+                    let (dx, dy) = points[i];
+                    if in_arc(dx, dy, start, sweep) {
+                        self.plot(cx + dx, cy + dy);
+                    }
+                    i += 1;
+                }
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+}
+
+/// Checks whether the offset (`dx`, `dy`) from a centre, in display coordinates, lies within `sweep` degrees
+/// counter-clockwise of `start`.
+const fn in_arc(dx: isize, dy: isize, start: i32, sweep: i32) -> bool {
+    if sweep >= 360 {
+        return true;
+    }
+    if dx == 0 && dy == 0 {
+        return true;
+    }
+    // Flip y so that angles increase counter-clockwise on the display.
+    let point = (dx as i64, -dy as i64);
+    let from = (cos(start) as i64, sin(start) as i64);
+    let to = (cos(start + sweep) as i64, sin(start + sweep) as i64);
+    const fn cross(a: (i64, i64), b: (i64, i64)) -> i64 {
+        a.0 * b.1 - a.1 * b.0
+    }
+    const fn dot(a: (i64, i64), b: (i64, i64)) -> i64 {
+        a.0 * b.0 + a.1 * b.1
+    }
+
+    if sweep <= 180 {
+        // Within the sweep when counter-clockwise of the start and clockwise of the end. The dot products rule out the
+        // point opposite a narrow sweep, which lies on both lines.
+        cross(from, point) >= 0
+            && cross(point, to) >= 0
+            && (dot(from, point) >= 0 || dot(to, point) >= 0)
+    } else {
+        // Outside of the narrower gap between the end and the start.
+        !(cross(to, point) > 0 && cross(point, from) > 0)
+    }
+}
+
+/// Sorts `start` and a possibly negative `sweep` so that the sweep runs counter-clockwise.
+pub(crate) const fn normalise_arc(start: i32, sweep: i32) -> (i32, i32) {
+    if sweep < 0 {
+        ((start + sweep).rem_euclid(360), -sweep)
+    } else {
+        (start.rem_euclid(360), sweep)
+    }
+}
+
+const fn full_display() -> Rect {
+    Rect::new(0, 0, DisplayArr::WIDTH, DisplayArr::LEN)
+}
+
+/// Draws a line from (`x0`, `y0`) to (`x1`, `y1`), including both ends. Pixels off the display are skipped, so the line
+/// may start or end outside of it.
+pub const fn draw_line(
+    display: &mut DisplayArr,
+    x0: isize,
+    y0: isize,
+    x1: isize,
+    y1: isize,
+    mode: DrawMode,
+) {
+    let mut shape = Shape::new(full_display());
+    shape.line(x0, y0, x1, y1);
+    shape.draw(display, mode);
+}
+
+/// Draws the one pixel wide outline of `rect`. Returns [DisplayArrErr::InvalidColumnError] or
+/// [DisplayArrErr::InvalidRowError] if `rect` does not lie within `display`.
+pub const fn draw_rect(
+    display: &mut DisplayArr,
+    rect: &Rect,
+    mode: DrawMode,
+) -> Result<(), DisplayArrErr> {
+    if let Err(e) = DisplayArr::check_rect(rect) {
+        return Err(e);
+    }
+    let mut shape = Shape::new(full_display());
+    shape.rect(rect);
+    shape.draw(display, mode);
+    Ok(())
+}
+
+/// Draws every pixel of `rect`. Returns [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if
+/// `rect` does not lie within `display`.
+pub const fn fill_rect(
+    display: &mut DisplayArr,
+    rect: &Rect,
+    mode: DrawMode,
+) -> Result<(), DisplayArrErr> {
+    if let Err(e) = DisplayArr::check_rect(rect) {
+        return Err(e);
+    }
+    let mask = DisplayArr::row_mask(rect.x, rect.width);
+    let mut y = rect.y;
+    while y < rect.bottom() {
+        if let Err(e) = apply_row(display, y, mask, mode) {
+            return Err(e);
+        }
+        y += 1;
+    }
+    Ok(())
+}
+
+/// Draws the outline of a circle centred on (`cx`, `cy`). Pixels off the display are skipped.
+pub const fn draw_circle(
+    display: &mut DisplayArr,
+    cx: isize,
+    cy: isize,
+    radius: isize,
+    mode: DrawMode,
+) {
+    let mut shape = Shape::new(full_display());
+    shape.circle(cx, cy, radius, 0, 360, false);
+    shape.draw(display, mode);
+}
+
+/// Draws a filled circle centred on (`cx`, `cy`). Pixels off the display are skipped.
+pub const fn fill_circle(
+    display: &mut DisplayArr,
+    cx: isize,
+    cy: isize,
+    radius: isize,
+    mode: DrawMode,
+) {
+    let mut shape = Shape::new(full_display());
+    shape.circle(cx, cy, radius, 0, 360, true);
+    shape.draw(display, mode);
+}
+
+/// Draws the outline of an ellipse centred on (`cx`, `cy`) with radii `rx` and `ry`. Pixels off the display are
+/// skipped.
+pub const fn draw_ellipse(
+    display: &mut DisplayArr,
+    cx: isize,
+    cy: isize,
+    rx: isize,
+    ry: isize,
+    mode: DrawMode,
+) {
+    let mut shape = Shape::new(full_display());
+    shape.ellipse(cx, cy, rx, ry, false);
+    shape.draw(display, mode);
+}
+
+/// Draws a filled ellipse centred on (`cx`, `cy`) with radii `rx` and `ry`. Pixels off the display are skipped.
+pub const fn fill_ellipse(
+    display: &mut DisplayArr,
+    cx: isize,
+    cy: isize,
+    rx: isize,
+    ry: isize,
+    mode: DrawMode,
+) {
+    let mut shape = Shape::new(full_display());
+    shape.ellipse(cx, cy, rx, ry, true);
+    shape.draw(display, mode);
+}
+
+/// Draws the part of a circle outline from `start` degrees, sweeping `sweep` degrees counter-clockwise (or clockwise
+/// if `sweep` is negative). Angles are measured as in [polar]. Pixels off the display are skipped.
+pub const fn draw_arc(
+    display: &mut DisplayArr,
+    cx: isize,
+    cy: isize,
+    radius: isize,
+    start: i32,
+    sweep: i32,
+    mode: DrawMode,
+) {
+    let (start, sweep) = normalise_arc(start, sweep);
+    let mut shape = Shape::new(full_display());
+    shape.circle(cx, cy, radius, start, sweep, false);
+    shape.draw(display, mode);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(display: &DisplayArr) -> Vec<(usize, usize)> {
+        let mut output = Vec::new();
+        for y in 0..DisplayArr::LEN {
+            let row = display.row(y).unwrap();
+            for x in 0..DisplayArr::WIDTH {
+                if row & (1u128 << (127 - x)) != 0 {
+                    output.push((x, y));
+                }
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(DrawMode::Set.apply(0b1100, 0b1010), 0b1110);
+        assert_eq!(DrawMode::Clear.apply(0b1100, 0b1010), 0b0100);
+        assert_eq!(DrawMode::Xor.apply(0b1100, 0b1010), 0b0110);
+    }
+
+    #[test]
+    fn trig() {
+        assert_eq!(sin(0), 0);
+        assert_eq!(sin(90), 1 << 14);
+        assert_eq!(sin(210), -(1 << 13));
+        assert_eq!(cos(-60), 1 << 13);
+        assert_eq!(polar(64, 32, 20, 0), (84, 32));
+        assert_eq!(polar(64, 32, 20, 90), (64, 12));
+        assert_eq!(polar(64, 32, 20, 225), (50, 46));
+    }
+
+    #[test]
+    fn lines() {
+        let mut display = DisplayArr::new();
+        draw_line(&mut display, 60, 3, 70, 3, DrawMode::Set);
+        assert_eq!(display.row(3).unwrap(), DisplayArr::row_mask(60, 11));
+
+        let mut display = DisplayArr::new();
+        draw_line(&mut display, 0, 0, 4, 4, DrawMode::Set);
+        assert_eq!(pixels(&display), [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+
+        let mut display = DisplayArr::new();
+        draw_line(&mut display, 0, 0, 4, 2, DrawMode::Set);
+        assert_eq!(pixels(&display), [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+
+        // Lines running off the display are clipped.
+        let mut display = DisplayArr::new();
+        draw_line(&mut display, -10, 70, 130, -5, DrawMode::Set);
+        assert!(!pixels(&display).is_empty());
+        draw_line(&mut display, -10, 70, 130, -5, DrawMode::Xor);
+        assert_eq!(display, DisplayArr::new());
+
+        let mut display = DisplayArr::new_full();
+        draw_line(&mut display, 5, 0, 5, 63, DrawMode::Clear);
+        assert_eq!(display.row(40).unwrap(), !DisplayArr::row_mask(5, 1));
+    }
+
+    #[test]
+    fn rects() {
+        let mut display = DisplayArr::new();
+        draw_rect(&mut display, &Rect::new(60, 10, 8, 4), DrawMode::Set).unwrap();
+        assert_eq!(display.row(10).unwrap(), DisplayArr::row_mask(60, 8));
+        assert_eq!(
+            display.row(11).unwrap(),
+            DisplayArr::row_mask(60, 1) | DisplayArr::row_mask(67, 1)
+        );
+        assert_eq!(display.row(13).unwrap(), DisplayArr::row_mask(60, 8));
+        assert_eq!(display.row(14).unwrap(), 0);
+
+        // A single row outline should not cancel itself out.
+        let mut display = DisplayArr::new();
+        draw_rect(&mut display, &Rect::new(0, 0, 5, 1), DrawMode::Xor).unwrap();
+        assert_eq!(display.row(0).unwrap(), DisplayArr::row_mask(0, 5));
+
+        let mut display = DisplayArr::new();
+        fill_rect(&mut display, &Rect::new(100, 60, 28, 4), DrawMode::Set).unwrap();
+        fill_rect(&mut display, &Rect::new(110, 62, 4, 1), DrawMode::Clear).unwrap();
+        assert_eq!(display.row(61).unwrap(), DisplayArr::row_mask(100, 28));
+        assert_eq!(
+            display.row(62).unwrap(),
+            DisplayArr::row_mask(100, 28) & !DisplayArr::row_mask(110, 4)
+        );
+
+        assert!(matches!(
+            fill_rect(&mut display, &Rect::new(100, 0, 29, 1), DrawMode::Set),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert!(matches!(
+            draw_rect(&mut display, &Rect::new(0, 60, 1, 5), DrawMode::Set),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+    }
+
+    #[test]
+    fn circles() {
+        let mut display = DisplayArr::new();
+        draw_circle(&mut display, 10, 10, 0, DrawMode::Set);
+        assert_eq!(pixels(&display), [(10, 10)]);
+
+        let mut display = DisplayArr::new();
+        draw_circle(&mut display, 64, 32, 10, DrawMode::Set);
+        let points = pixels(&display);
+        for &(x, y) in &points {
+            let distance = ((x as f64 - 64.0).powi(2) + (y as f64 - 32.0).powi(2)).sqrt();
+            assert!((distance - 10.0).abs() < 0.75);
+            assert!(points.contains(&(128 - x, y)));
+            assert!(points.contains(&(x, 64 - y)));
+        }
+        assert!(points.contains(&(74, 32)) && points.contains(&(64, 22)));
+
+        // Every pixel is drawn once, so xor twice cancels out.
+        draw_circle(&mut display, 64, 32, 10, DrawMode::Xor);
+        assert_eq!(display, DisplayArr::new());
+
+        let mut display = DisplayArr::new();
+        fill_circle(&mut display, 64, 32, 10, DrawMode::Set);
+        assert_eq!(display.row(32).unwrap(), DisplayArr::row_mask(54, 21));
+        assert_eq!(display.row(22).unwrap().count_ones(), 7);
+        assert_eq!(display.row(21).unwrap(), 0);
+
+        let mut clipped = DisplayArr::new();
+        fill_circle(&mut clipped, 0, 0, 3, DrawMode::Set);
+        assert_eq!(clipped.row(0).unwrap(), DisplayArr::row_mask(0, 4));
+    }
+
+    #[test]
+    fn ellipses() {
+        let mut display = DisplayArr::new();
+        draw_ellipse(&mut display, 64, 32, 20, 8, DrawMode::Set);
+        let points = pixels(&display);
+        assert!(points.contains(&(44, 32)) && points.contains(&(84, 32)));
+        assert!(points.contains(&(64, 24)) && points.contains(&(64, 40)));
+        assert!(points
+            .iter()
+            .all(|&(x, y)| (44..=84).contains(&x) && (24..=40).contains(&y)));
+        draw_ellipse(&mut display, 64, 32, 20, 8, DrawMode::Xor);
+        assert_eq!(display, DisplayArr::new());
+
+        let mut display = DisplayArr::new();
+        fill_ellipse(&mut display, 64, 32, 20, 8, DrawMode::Set);
+        assert_eq!(display.row(32).unwrap(), DisplayArr::row_mask(44, 41));
+        assert_eq!(display.row(23).unwrap(), 0);
+
+        let mut flat = DisplayArr::new();
+        draw_ellipse(&mut flat, 10, 5, 3, 0, DrawMode::Set);
+        assert_eq!(flat.row(5).unwrap(), DisplayArr::row_mask(7, 7));
+    }
+
+    #[test]
+    fn arcs() {
+        let mut display = DisplayArr::new();
+        draw_arc(&mut display, 64, 32, 12, 0, 90, DrawMode::Set);
+        let points = pixels(&display);
+        assert!(points.contains(&(76, 32)) && points.contains(&(64, 20)));
+        assert!(points.iter().all(|&(x, y)| x >= 64 && y <= 32));
+
+        // A negative sweep runs clockwise.
+        let mut clockwise = DisplayArr::new();
+        draw_arc(&mut clockwise, 64, 32, 12, 90, -90, DrawMode::Set);
+        assert_eq!(clockwise, display);
+
+        // A 270 degree dial leaves the bottom quarter open.
+        let mut dial = DisplayArr::new();
+        draw_arc(&mut dial, 64, 32, 12, 225, -270, DrawMode::Set);
+        let points = pixels(&dial);
+        assert!(points.contains(&(52, 32)) && points.contains(&(76, 32)));
+        assert!(!points.contains(&(64, 44)));
+
+        let mut full = DisplayArr::new();
+        draw_circle(&mut full, 64, 32, 12, DrawMode::Set);
+        let mut arc = DisplayArr::new();
+        draw_arc(&mut arc, 64, 32, 12, 30, 360, DrawMode::Set);
+        assert_eq!(arc, full);
+
+        let mut narrow = DisplayArr::new();
+        draw_arc(&mut narrow, 64, 32, 12, 0, 0, DrawMode::Set);
+        assert_eq!(pixels(&narrow), [(76, 32)]);
+    }
+}
@@ -0,0 +1,688 @@
+use core::fmt::Formatter;
+use core::option::Option;
+use core::option::Option::None;
+use core::option::Option::Some;
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+use core::write;
+
+use crate::text::{GlyphSource, TextLayout};
+
+/// First character stored in a [BitmapFont].
+pub const FIRST_CHAR: u8 = 0x20;
+/// Number of characters stored in a [BitmapFont], covering printable ASCII.
+pub const GLYPH_COUNT: usize = 95;
+/// Widest glyph cell a [BitmapFont] can hold.
+pub const MAX_GLYPH_WIDTH: usize = 32;
+/// Tallest glyph cell a [BitmapFont] can hold, which is also the tallest line a [TextLayout] can draw.
+pub const MAX_GLYPH_HEIGHT: usize = 32;
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+
+/// Error variants for parsing a [BitmapFont].
+pub enum FontError {
+    /// The data is not a PSF1, PSF2 or BDF font.
+    UnknownFormat,
+    /// The data ended early. Argument is the length the data needed to be.
+    Truncated(usize),
+    /// The glyph cells are larger than the packed format can hold. Arguments are the width and height of the cells.
+    GlyphTooLarge(usize, usize),
+    /// A header field or BDF line is invalid. Argument is the byte offset of the header field, or the line number.
+    Malformed(usize),
+}
+
+impl core::fmt::Display for FontError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FontError::UnknownFormat => write!(f, "Font is not in PSF1, PSF2 or BDF format"),
+            FontError::Truncated(length) => {
+                write!(
+                    f,
+                    "Font data ended early: expected at least {:} bytes",
+                    length
+                )
+            }
+            FontError::GlyphTooLarge(width, height) => write!(
+                f,
+                "Font glyphs are too large: {:}x{:} does not fit in a packed glyph",
+                width, height
+            ),
+            FontError::Malformed(position) => write!(f, "Font data is malformed at {:}", position),
+        }
+    }
+}
+
+impl core::fmt::Debug for FontError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// A font for printable ASCII, imported from a PSF or BDF file. Every glyph shares one cell size, at most
+/// [MAX_GLYPH_WIDTH] x `H` (and no more than [MAX_GLYPH_HEIGHT] tall). Both parsers are `const`, so a font can be built
+/// from `include_bytes!` into a `static` at compile time, and drawn with the [TextLayout] from [Self::layout].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BitmapFont<const H: usize> {
+    /// Width of every glyph cell.
+    pub width: usize,
+    /// Height of every glyph cell.
+    pub height: usize,
+    /// Rows from the top of a cell down to the baseline.
+    pub ascent: usize,
+    /// Columns from the left edge of each glyph's cell to the left edge of the next one, for [FIRST_CHAR] onwards.
+    pub advances: [usize; GLYPH_COUNT],
+    /// Rows of each glyph's cell for [FIRST_CHAR] onwards, where 0 is the top row. The leftmost pixel is bit
+    /// `width - 1`. Characters missing from the font are blank.
+    pub rows: [[u32; H]; GLYPH_COUNT],
+}
+
+impl<const H: usize> BitmapFont<H> {
+    /// Creates a font where every glyph is blank.
+    pub const fn new(width: usize, height: usize, ascent: usize) -> Self {
+        Self {
+            width,
+            height,
+            ascent,
+            advances: [width; GLYPH_COUNT],
+            rows: [[0; H]; GLYPH_COUNT],
+        }
+    }
+
+    /// Parses a PC Screen Font, either version 1 or 2. Glyphs are looked up by their index, so the font should put
+    /// ASCII characters at their own codes.
+    pub const fn from_psf(data: &[u8]) -> Result<Self, FontError> {
+        let (width, height, count, charsize, offset) = if starts_with(data, &PSF1_MAGIC) {
+            if data.len() < 4 {
+                return Err(FontError::Truncated(4));
+            }
+            let count = if data[2] & 0x01 != 0 { 512 } else { 256 };
+            (8, data[3] as usize, count, data[3] as usize, 4)
+        } else if starts_with(data, &PSF2_MAGIC) {
+            if data.len() < 32 {
+                return Err(FontError::Truncated(32));
+            }
+            (
+                read_u32_le(data, 28),
+                read_u32_le(data, 24),
+                read_u32_le(data, 16),
+                read_u32_le(data, 20),
+                read_u32_le(data, 8),
+            )
+        } else {
+            return Err(FontError::UnknownFormat);
+        };
+
+        if width == 0 || width > MAX_GLYPH_WIDTH || height > H || height > MAX_GLYPH_HEIGHT {
+            return Err(FontError::GlyphTooLarge(width, height));
+        }
+        let row_bytes = width.div_ceil(8);
+        if row_bytes * height > charsize {
+            return Err(FontError::Malformed(20));
+        }
+
+        let mut font = Self::new(width, height, height);
+        let mut i: usize = 0;
+        while i < GLYPH_COUNT {
+            let index = FIRST_CHAR as usize + i;
+            if index < count {
+                let start = offset + index * charsize;
+                if start + charsize > data.len() {
+                    return Err(FontError::Truncated(start + charsize));
+                }
+                let mut row: usize = 0;
+                while row < height {
+                    let mut value: u32 = 0;
+                    let mut byte: usize = 0;
+                    while byte < row_bytes {
+                        value = (value << 8) | data[start + row * row_bytes + byte] as u32;
+                        byte += 1;
+                    }
+                    font.rows[i][row] = value >> (row_bytes * 8 - width);
+                    row += 1;
+                }
+            }
+            i += 1;
+        }
+        Ok(font)
+    }
+
+    /// Parses a Glyph Bitmap Distribution Format font. Cells take the size of the `FONTBOUNDINGBOX`, with each glyph
+    /// placed in its cell according to its `BBX`.
+    pub const fn from_bdf(data: &[u8]) -> Result<Self, FontError> {
+        let mut font = Self::new(0, 0, 0);
+        // Offsets of the cell's bottom-left corner from the glyph origin.
+        let (mut cell_x, mut cell_y): (isize, isize) = (0, 0);
+        let mut ascent: Option<usize> = None;
+        let mut found_bounds = false;
+
+        let mut encoding: isize = -1;
+        let mut advance: usize = 0;
+        // Glyph width, height and column and row of its top-left corner in the cell.
+        let mut bbx: (usize, usize, usize, usize) = (0, 0, 0, 0);
+        let mut bitmap_row: Option<usize> = None;
+        let mut glyph: [u32; H] = [0; H];
+
+        let mut pos: usize = 0;
+        let mut line_number: usize = 0;
+        while pos < data.len() {
+            let (start, end, next) = next_line(data, pos);
+            pos = next;
+            line_number += 1;
+
+            if line_number == 1 && !has_keyword(data, start, end, b"STARTFONT") {
+                return Err(FontError::UnknownFormat);
+            }
+
+            if let Some(row) = bitmap_row {
+                if has_keyword(data, start, end, b"ENDCHAR") {
+                    if encoding >= FIRST_CHAR as isize
+                        && encoding < FIRST_CHAR as isize + GLYPH_COUNT as isize
+                    {
+                        let i = encoding as usize - FIRST_CHAR as usize;
+                        font.advances[i] = advance;
+                        font.rows[i] = glyph;
+                    }
+                    bitmap_row = None;
+                    continue;
+                }
+                if row >= bbx.1 {
+                    return Err(FontError::Malformed(line_number));
+                }
+                let value = match parse_hex(data, start, end) {
+                    Some(value) => value,
+                    None => return Err(FontError::Malformed(line_number)),
+                };
+                let bytes = bbx.0.div_ceil(8);
+                let bits = (value >> (bytes * 8 - bbx.0)) as u32;
+                glyph[bbx.3 + row] = bits << (font.width - bbx.2 - bbx.0);
+                bitmap_row = Some(row + 1);
+                continue;
+            }
+
+            if has_keyword(data, start, end, b"FONTBOUNDINGBOX") {
+                let [width, height, x, y] = match parse_ints::<4>(data, start, end) {
+                    Some(values) => values,
+                    None => return Err(FontError::Malformed(line_number)),
+                };
+                if width <= 0 || height <= 0 {
+                    return Err(FontError::Malformed(line_number));
+                }
+                if width as usize > MAX_GLYPH_WIDTH
+                    || height as usize > H
+                    || height as usize > MAX_GLYPH_HEIGHT
+                {
+                    return Err(FontError::GlyphTooLarge(width as usize, height as usize));
+                }
+                let ascent = font.ascent;
+                font = Self::new(width as usize, height as usize, ascent);
+                (cell_x, cell_y) = (x, y);
+                found_bounds = true;
+            } else if has_keyword(data, start, end, b"FONT_ASCENT") {
+                match parse_ints::<1>(data, start, end) {
+                    Some([value]) if value >= 0 => ascent = Some(value as usize),
+                    _ => return Err(FontError::Malformed(line_number)),
+                }
+            } else if has_keyword(data, start, end, b"STARTCHAR") {
+                if !found_bounds {
+                    return Err(FontError::Malformed(line_number));
+                }
+                encoding = -1;
+                advance = font.width;
+                bbx = (font.width, font.height, 0, 0);
+            } else if has_keyword(data, start, end, b"ENCODING") {
+                match parse_ints::<1>(data, start, end) {
+                    Some([value]) => encoding = value,
+                    None => return Err(FontError::Malformed(line_number)),
+                }
+            } else if has_keyword(data, start, end, b"DWIDTH") {
+                match parse_ints::<2>(data, start, end) {
+                    Some([value, _]) if value >= 0 => advance = value as usize,
+                    _ => return Err(FontError::Malformed(line_number)),
+                }
+            } else if has_keyword(data, start, end, b"BBX") {
+                let [width, height, x, y] = match parse_ints::<4>(data, start, end) {
+                    Some(values) => values,
+                    None => return Err(FontError::Malformed(line_number)),
+                };
+                let column = x - cell_x;
+                let row = (cell_y + font.height as isize) - (y + height);
+                if width < 0
+                    || height < 0
+                    || column < 0
+                    || row < 0
+                    || (column + width) as usize > font.width
+                    || (row + height) as usize > font.height
+                {
+                    return Err(FontError::Malformed(line_number));
+                }
+                bbx = (
+                    width as usize,
+                    height as usize,
+                    column as usize,
+                    row as usize,
+                );
+            } else if has_keyword(data, start, end, b"BITMAP") {
+                glyph = [0; H];
+                bitmap_row = Some(0);
+            }
+        }
+
+        if !found_bounds {
+            return Err(FontError::Truncated(data.len() + 1));
+        }
+        font.ascent = match ascent {
+            Some(ascent) => ascent,
+            None => (font.height as isize + cell_y) as usize,
+        };
+        Ok(font)
+    }
+
+    /// Gets the rows of the glyph for `character`, or [None] if it is not printable ASCII.
+    pub const fn glyph(&self, character: u8) -> Option<&[u32; H]> {
+        match glyph_index(character) {
+            Some(i) => Some(&self.rows[i]),
+            None => None,
+        }
+    }
+
+    /// Gets the distance from the start of `character` to the start of the next character.
+    pub const fn advance(&self, character: u8) -> usize {
+        match glyph_index(character) {
+            Some(i) => self.advances[i],
+            None => self.width,
+        }
+    }
+
+    /// Borrows the glyphs of `self` in the form [TextLayout] draws from.
+    pub const fn glyphs(&self) -> FontGlyphs<'_> {
+        FontGlyphs {
+            width: self.width,
+            height: self.height,
+            advances: &self.advances,
+            rows: self.rows.as_flattened(),
+            pitch: H,
+        }
+    }
+
+    /// Gets a [TextLayout] which draws in `self`, with characters as far apart as the font advances them, no extra
+    /// letter spacing and no kerning. Proportional text uses the advance of a space for blank characters.
+    pub const fn layout(&self) -> TextLayout<'_> {
+        TextLayout {
+            letter_spacing: 0,
+            proportional: false,
+            space_width: self.advance(b' '),
+            kerning: &[],
+            glyphs: GlyphSource::Imported(self.glyphs()),
+        }
+    }
+}
+
+/// The glyphs of a [BitmapFont], without its height in their type. Made by [BitmapFont::glyphs].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FontGlyphs<'a> {
+    /// Width of every glyph cell.
+    pub width: usize,
+    /// Height of every glyph cell.
+    pub height: usize,
+    advances: &'a [usize; GLYPH_COUNT],
+    /// Rows of every glyph, one glyph after another, with `pitch` rows each.
+    rows: &'a [u32],
+    pitch: usize,
+}
+
+impl FontGlyphs<'_> {
+    /// Gets the distance from the start of `character` to the start of the next character.
+    pub const fn advance(&self, character: u8) -> usize {
+        match glyph_index(character) {
+            Some(i) => self.advances[i],
+            None => self.width,
+        }
+    }
+
+    /// Gets row `idx` of `character`, where 0 is the top row of the cell and the leftmost pixel is bit
+    /// `self.width - 1`. Rows past the bottom of the cell, and characters which are not printable ASCII, are blank.
+    pub const fn row_bits(&self, character: u8, idx: usize) -> u32 {
+        match glyph_index(character) {
+            Some(i) if idx < self.height => self.rows[i * self.pitch + idx],
+            _ => 0,
+        }
+    }
+
+    /// Gets the number of blank columns on the left of `character`, and the width of the columns between its leftmost
+    /// and rightmost lit pixels. Blank glyphs have an inked width of 0.
+    pub const fn ink_bounds(&self, character: u8) -> (usize, usize) {
+        let mut mask: u32 = 0;
+        let mut i: usize = 0;
+        while i < self.height {
+            mask |= self.row_bits(character, i);
+            i += 1;
+        }
+        if mask == 0 {
+            return (0, 0);
+        }
+        let highest = 31 - mask.leading_zeros() as usize;
+        let lowest = mask.trailing_zeros() as usize;
+        (self.width - 1 - highest, highest + 1 - lowest)
+    }
+}
+
+/// Gets the index of `character` in the glyphs of a [BitmapFont], or [None] if it is not printable ASCII.
+const fn glyph_index(character: u8) -> Option<usize> {
+    if character < FIRST_CHAR || character as usize >= FIRST_CHAR as usize + GLYPH_COUNT {
+        return None;
+    }
+    Some((character - FIRST_CHAR) as usize)
+}
+
+/// Checks whether `data` starts with `prefix`.
+const fn starts_with(data: &[u8], prefix: &[u8]) -> bool {
+    if data.len() < prefix.len() {
+        return false;
+    }
+    let mut i: usize = 0;
+    while i < prefix.len() {
+        if data[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn read_u32_le(data: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ]) as usize
+}
+
+/// Finds the line starting at `pos`. Returns its start, its end (excluding any line terminator), and the start of the
+/// next line.
+const fn next_line(data: &[u8], pos: usize) -> (usize, usize, usize) {
+    let mut end = pos;
+    while end < data.len() && data[end] != b'\n' {
+        end += 1;
+    }
+    let next = if end < data.len() { end + 1 } else { end };
+    let mut trimmed = end;
+    while trimmed > pos && (data[trimmed - 1] == b'\r' || data[trimmed - 1] == b' ') {
+        trimmed -= 1;
+    }
+    (pos, trimmed, next)
+}
+
+/// Checks whether the line `data[start..end]` starts with the word `keyword`.
+const fn has_keyword(data: &[u8], start: usize, end: usize, keyword: &[u8]) -> bool {
+    if end - start < keyword.len() {
+        return false;
+    }
+    let mut i: usize = 0;
+    while i < keyword.len() {
+        if data[start + i] != keyword[i] {
+            return false;
+        }
+        i += 1;
+    }
+    start + keyword.len() == end || data[start + keyword.len()] == b' '
+}
+
+/// Parses the `N` integers following the keyword on the line `data[start..end]`. Returns [None] if any of them is
+/// missing, is not a number, or does not fit in an [isize].
+const fn parse_ints<const N: usize>(data: &[u8], start: usize, end: usize) -> Option<[isize; N]> {
+    let mut output = [0isize; N];
+    let mut pos = start;
+    // Skip the keyword.
+    while pos < end && data[pos] != b' ' {
+        pos += 1;
+    }
+
+    let mut i: usize = 0;
+    while i < N {
+        while pos < end && data[pos] == b' ' {
+            pos += 1;
+        }
+        let negative = pos < end && data[pos] == b'-';
+        if negative {
+            pos += 1;
+        }
+        let digits_start = pos;
+        let mut value: isize = 0;
+        while pos < end && data[pos].is_ascii_digit() {
+            value = match value.checked_mul(10) {
+                Some(value) => match value.checked_add((data[pos] - b'0') as isize) {
+                    Some(value) => value,
+                    None => return None,
+                },
+                None => return None,
+            };
+            pos += 1;
+        }
+        if pos == digits_start || (pos < end && data[pos] != b' ') {
+            return None;
+        }
+        output[i] = if negative { -value } else { value };
+        i += 1;
+    }
+    Some(output)
+}
+
+/// Parses the line `data[start..end]` as a hexadecimal number of at most 16 digits.
+const fn parse_hex(data: &[u8], start: usize, end: usize) -> Option<u64> {
+    if start == end || end - start > 16 {
+        return None;
+    }
+    let mut value: u64 = 0;
+    let mut pos = start;
+    while pos < end {
+        let digit = match data[pos] {
+            b'0'..=b'9' => data[pos] - b'0',
+            b'a'..=b'f' => data[pos] - b'a' + 10,
+            b'A'..=b'F' => data[pos] - b'A' + 10,
+            _ => return None,
+        };
+        value = (value << 4) | digit as u64;
+        pos += 1;
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newspeed::{DisplayArr, DisplayArrErr};
+
+    const BDF: &[u8] = b"STARTFONT 2.1\r
+FONT -test-tiny-medium-r-normal--7-70-75-75-c-60-iso10646-1\r
+SIZE 7 75 75\r
+FONTBOUNDINGBOX 6 7 0 -1\r
+STARTPROPERTIES 2\r
+FONT_ASCENT 6\r
+FONT_DESCENT 1\r
+ENDPROPERTIES\r
+CHARS 3\r
+STARTCHAR A\r
+ENCODING 65\r
+SWIDTH 857 0\r
+DWIDTH 6 0\r
+BBX 5 6 0 0\r
+BITMAP\r
+20\r
+50\r
+88\r
+F8\r
+88\r
+88\r
+ENDCHAR\r
+STARTCHAR g\r
+ENCODING 103\r
+DWIDTH 5 0\r
+BBX 4 5 1 -1\r
+BITMAP\r
+70\r
+90\r
+70\r
+10\r
+60\r
+ENDCHAR\r
+STARTCHAR uni00E9\r
+ENCODING 233\r
+DWIDTH 6 0\r
+BBX 1 1 0 0\r
+BITMAP\r
+80\r
+ENDCHAR\r
+ENDFONT\r
+";
+
+    const TINY: BitmapFont<8> = match BitmapFont::from_bdf(BDF) {
+        Ok(font) => font,
+        Err(_) => panic!("test font does not parse"),
+    };
+
+    /// Builds a PSF2 font of 128 glyphs, where glyph `i` has every row set to `i`.
+    fn psf2(width: usize, height: usize) -> Vec<u8> {
+        let row_bytes = width.div_ceil(8);
+        let charsize = row_bytes * height;
+        let mut data = Vec::new();
+        data.extend_from_slice(&PSF2_MAGIC);
+        for field in [0, 32, 0, 128, charsize, height, width] {
+            data.extend_from_slice(&(field as u32).to_le_bytes());
+        }
+        for glyph in 0..128usize {
+            for _ in 0..height {
+                let row = (glyph as u64) << (row_bytes * 8 - width);
+                data.extend_from_slice(&row.to_be_bytes()[8 - row_bytes..]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn bdf_glyphs() {
+        assert_eq!((TINY.width, TINY.height, TINY.ascent), (6, 7, 6));
+
+        let a = TINY.glyph(b'A').unwrap();
+        assert_eq!(TINY.advance(b'A'), 6);
+        assert_eq!(
+            a[..7],
+            [0b001000, 0b010100, 0b100010, 0b111110, 0b100010, 0b100010, 0]
+        );
+
+        // `g` starts one column in, and its descender reaches the bottom row of the cell.
+        let g = TINY.glyph(b'g').unwrap();
+        assert_eq!(TINY.advance(b'g'), 5);
+        assert_eq!(
+            g[..7],
+            [0, 0, 0b001110, 0b010010, 0b001110, 0b000010, 0b001100]
+        );
+
+        assert_eq!(*TINY.glyph(b'B').unwrap(), [0; 8]);
+        assert!(TINY.glyph(0x7F).is_none());
+    }
+
+    #[test]
+    fn bdf_errors() {
+        assert!(matches!(
+            BitmapFont::<8>::from_bdf(b"FONT x\n"),
+            Err(FontError::UnknownFormat)
+        ));
+        assert!(matches!(
+            BitmapFont::<4>::from_bdf(BDF),
+            Err(FontError::GlyphTooLarge(6, 7))
+        ));
+        assert!(matches!(
+            BitmapFont::<8>::from_bdf(b"STARTFONT 2.1\nSTARTCHAR A\n"),
+            Err(FontError::Malformed(2))
+        ));
+        assert!(matches!(
+            BitmapFont::<8>::from_bdf(b"STARTFONT 2.1\nFONTBOUNDINGBOX 6 x 0 0\n"),
+            Err(FontError::Malformed(2))
+        ));
+        assert!(matches!(
+            BitmapFont::<8>::from_bdf(
+                b"STARTFONT 2.1\nFONTBOUNDINGBOX 4 4 0 0\nSTARTCHAR A\nBBX 5 1 0 0\n"
+            ),
+            Err(FontError::Malformed(4))
+        ));
+        assert!(matches!(
+            BitmapFont::<8>::from_bdf(
+                b"STARTFONT 2.1\nFONTBOUNDINGBOX 99999999999999999999 4 0 0\n"
+            ),
+            Err(FontError::Malformed(2))
+        ));
+    }
+
+    #[test]
+    fn psf_glyphs() {
+        let font = BitmapFont::<16>::from_psf(&psf2(5, 9)).unwrap();
+        assert_eq!((font.width, font.height), (5, 9));
+        assert_eq!(font.glyph(b'A').unwrap()[..9], [b'A' as u32 & 0b11111; 9]);
+        assert_eq!(font.glyph(b'A').unwrap()[9..], [0; 7]);
+
+        let font = BitmapFont::<16>::from_psf(&psf2(12, 16)).unwrap();
+        assert_eq!(*font.glyph(b'~').unwrap(), [b'~' as u32; 16]);
+
+        let mut psf1 = vec![0x36, 0x04, 0x00, 8];
+        for glyph in 0..256usize {
+            psf1.extend_from_slice(&[glyph as u8; 8]);
+        }
+        let font = BitmapFont::<8>::from_psf(&psf1).unwrap();
+        assert_eq!((font.width, font.height), (8, 8));
+        assert_eq!(*font.glyph(b'z').unwrap(), [b'z' as u32; 8]);
+    }
+
+    #[test]
+    fn psf_errors() {
+        assert!(matches!(
+            BitmapFont::<8>::from_psf(b"STARTFONT"),
+            Err(FontError::UnknownFormat)
+        ));
+        assert!(matches!(
+            BitmapFont::<8>::from_psf(&psf2(5, 9)),
+            Err(FontError::GlyphTooLarge(5, 9))
+        ));
+        assert!(matches!(
+            BitmapFont::<64>::from_psf(&psf2(33, 9)),
+            Err(FontError::GlyphTooLarge(33, 9))
+        ));
+        let data = psf2(5, 9);
+        assert!(matches!(
+            BitmapFont::<16>::from_psf(&data[..200]),
+            Err(FontError::Truncated(_))
+        ));
+        assert!(matches!(
+            BitmapFont::<16>::from_psf(&data[..20]),
+            Err(FontError::Truncated(32))
+        ));
+    }
+
+    #[test]
+    fn draw_imported() {
+        let layout = TINY.layout();
+        let mut display = DisplayArr::new();
+        assert_eq!(layout.measure(b"Ag").width, 12);
+        assert_eq!(layout.measure(b"Ag").height, 7);
+        assert_eq!(layout.draw(&mut display, b"Ag", 100, 10).unwrap(), 112);
+        assert_eq!(display.row(10).unwrap(), 0b001000u128 << 22);
+        assert_eq!(
+            display.row(13).unwrap(),
+            (0b111110u128 << 22) | (0b010010u128 << 16)
+        );
+        assert_eq!(display.row(16).unwrap(), 0b001100u128 << 16);
+
+        let mut scaled = DisplayArr::new();
+        layout.draw_scaled(&mut scaled, b"A", 0, 0, 2).unwrap();
+        assert_eq!(scaled.row(0).unwrap(), 0b000011000000u128 << 116);
+        assert_eq!(scaled.row(1).unwrap(), 0b000011000000u128 << 116);
+
+        assert!(matches!(
+            layout.draw(&mut display, b"Ag", 117, 0),
+            Err(DisplayArrErr::TextOverflowError(_))
+        ));
+        assert!(layout.draw(&mut display, b"A", 0, 58).is_err());
+    }
+}
@@ -0,0 +1,555 @@
+use core::fmt::Formatter;
+use core::fmt::Write;
+use core::option::Option;
+use core::option::Option::None;
+use core::option::Option::Some;
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+use core::write;
+use core::writeln;
+
+use crate::newspeed::{DisplayArr, DisplayArrErr, Framebuffer};
+
+/// Bytes in a binary PBM of a [DisplayArr] made by [to_p4]. See [p4_len].
+pub const P4_LEN: usize = p4_len(DisplayArr::WIDTH, DisplayArr::LEN);
+/// Pixels written on each line of a plain PBM, which should have lines of at most 70 characters.
+const P1_LINE: usize = 64;
+/// Bytes written on each line of an XBM.
+const XBM_LINE: usize = 12;
+
+/// Error variants for importing an image.
+pub enum ImageError {
+    /// The data is not a PBM or XBM image.
+    UnknownFormat,
+    /// The data ended early. Argument is the length the data needed to be.
+    Truncated(usize),
+    /// A header field or pixel is invalid. Argument is its byte offset.
+    Malformed(usize),
+    /// The image is larger than the [Framebuffer] it is imported into. Holds [DisplayArrErr::InvalidColumnError] for the last column of the
+    /// image or [DisplayArrErr::InvalidRowError] for the last row.
+    Dimensions(DisplayArrErr),
+}
+
+impl core::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ImageError::UnknownFormat => write!(f, "Image is not in PBM or XBM format"),
+            ImageError::Truncated(length) => write!(
+                f,
+                "Image data ended early: expected at least {:} bytes",
+                length
+            ),
+            ImageError::Malformed(position) => {
+                write!(f, "Image data is malformed at {:}", position)
+            }
+            ImageError::Dimensions(e) => write!(f, "Image does not fit: {}", e),
+        }
+    }
+}
+
+impl core::fmt::Debug for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Gets the number of decimal digits in `value`.
+const fn decimal_len(value: usize) -> usize {
+    let mut length: usize = 1;
+    let mut rest = value / 10;
+    while rest > 0 {
+        length += 1;
+        rest /= 10;
+    }
+    length
+}
+
+/// Writes `value` in decimal to `output` from `i`, returning the index after it.
+const fn write_decimal(output: &mut [u8], i: usize, value: usize) -> usize {
+    let end = i + decimal_len(value);
+    let mut rest = value;
+    let mut k = end;
+    while k > i {
+        k -= 1;
+        output[k] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+    }
+    end
+}
+
+/// Gets the length of the header of a binary PBM of a `width` x `height` image, such as `P4\n128 64\n`.
+const fn p4_header_len(width: usize, height: usize) -> usize {
+    3 + decimal_len(width) + 1 + decimal_len(height) + 1
+}
+
+/// Gets the length of a binary PBM of a `width` x `height` image made by [to_p4]: its header, then each row in
+/// `width.div_ceil(8)` bytes.
+pub const fn p4_len(width: usize, height: usize) -> usize {
+    p4_header_len(width, height) + width.div_ceil(8) * height
+}
+
+/// Checks that a `width` x `height` image fits in a `W` x `H` [Framebuffer].
+const fn check_size<const W: usize, const H: usize>(
+    width: usize,
+    height: usize,
+) -> Result<(), ImageError> {
+    if width > W {
+        return Err(ImageError::Dimensions(DisplayArrErr::column_err(
+            W - 1,
+            width - 1,
+        )));
+    }
+    if height > H {
+        return Err(ImageError::Dimensions(DisplayArrErr::row_err(
+            H - 1,
+            height - 1,
+        )));
+    }
+    Ok(())
+}
+
+/// Sets byte `idx` of a row in the same form as [Framebuffer::row_words], like [Framebuffer::row_byte].
+const fn set_word_byte(words: &mut [u64], idx: usize, byte: u8) {
+    words[idx / 8] |= (byte as u64) << (56 - 8 * (idx % 8));
+}
+
+/// Clears the columns from `width` onwards of a row in the same form as [Framebuffer::row_words].
+const fn clip_words(words: &mut [u64], width: usize) {
+    let mut i: usize = 0;
+    while i < words.len() {
+        let start = i * 64;
+        if start >= width {
+            words[i] = 0;
+        } else if width < start + 64 {
+            words[i] &= u64::MAX << (start + 64 - width);
+        }
+        i += 1;
+    }
+}
+
+/// Skips whitespace and `#` comments from `i`, returning the index of the next other byte.
+const fn skip_space(data: &[u8], mut i: usize) -> usize {
+    while i < data.len() {
+        if data[i] == b'#' {
+            while i < data.len() && data[i] != b'\n' {
+                i += 1;
+            }
+        } else if data[i].is_ascii_whitespace() {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+/// Parses a decimal number after any whitespace from `i`. Returns the number and the index after it.
+const fn parse_uint(data: &[u8], i: usize) -> Result<(usize, usize), ImageError> {
+    let mut i = skip_space(data, i);
+    if i >= data.len() {
+        return Err(ImageError::Truncated(i + 1));
+    }
+    let start = i;
+    let mut value: usize = 0;
+    while i < data.len() && data[i].is_ascii_digit() {
+        // Anything this large is far too big for a display anyway.
+        if value > u16::MAX as usize {
+            return Err(ImageError::Malformed(start));
+        }
+        value = value * 10 + (data[i] - b'0') as usize;
+        i += 1;
+    }
+    if i == start {
+        return Err(ImageError::Malformed(start));
+    }
+    Ok((value, i))
+}
+
+/// Finds `pattern` in `data` from `i`, returning the index after it.
+const fn find(data: &[u8], pattern: &[u8], mut i: usize) -> Option<usize> {
+    while i + pattern.len() <= data.len() {
+        let mut k: usize = 0;
+        while k < pattern.len() && data[i + k] == pattern[k] {
+            k += 1;
+        }
+        if k == pattern.len() {
+            return Some(i + pattern.len());
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Gets the value of a hex digit, or [None] if `byte` is not one.
+const fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Imports a netpbm bitmap, either plain (P1) or binary (P4), into the top-left corner of a new [Framebuffer]. Set
+/// pixels are black in the image. Images smaller than the framebuffer leave the rest of it clear, and larger ones
+/// return [ImageError::Dimensions]. This is `const`, so screens can be built at compile time with `include_bytes!`.
+pub const fn from_pbm<const W: usize, const H: usize, const S: usize>(
+    data: &[u8],
+) -> Result<Framebuffer<W, H, S>, ImageError> {
+    if data.len() < 2 || data[0] != b'P' || (data[1] != b'1' && data[1] != b'4') {
+        return Err(ImageError::UnknownFormat);
+    }
+    let (width, i) = match parse_uint(data, 2) {
+        Ok(field) => field,
+        Err(e) => return Err(e),
+    };
+    let (height, mut i) = match parse_uint(data, i) {
+        Ok(field) => field,
+        Err(e) => return Err(e),
+    };
+    if let Err(e) = check_size::<W, H>(width, height) {
+        return Err(e);
+    }
+
+    let mut output = Framebuffer::new();
+    let mut y: usize = 0;
+    if data[1] == b'1' {
+        while y < height {
+            let mut row = [0u64; S];
+            let mut x: usize = 0;
+            while x < width {
+                i = skip_space(data, i);
+                if i >= data.len() {
+                    return Err(ImageError::Truncated(i + 1));
+                }
+                match data[i] {
+                    b'1' => row[x / 64] |= 1 << (63 - x % 64),
+                    b'0' => {}
+                    _ => return Err(ImageError::Malformed(i)),
+                }
+                i += 1;
+                x += 1;
+            }
+            // Rows below the checked height are always in bounds.
+            let _ = output.set_row_words(y, row);
+            y += 1;
+        }
+    } else {
+        // The header ends with exactly one whitespace byte.
+        if i >= data.len() {
+            return Err(ImageError::Truncated(i + 1));
+        }
+        if !data[i].is_ascii_whitespace() {
+            return Err(ImageError::Malformed(i));
+        }
+        i += 1;
+        let row_bytes = width.div_ceil(8);
+        if data.len() < i + row_bytes * height {
+            return Err(ImageError::Truncated(i + row_bytes * height));
+        }
+        while y < height {
+            let mut row = [0u64; S];
+            let mut k: usize = 0;
+            while k < row_bytes {
+                set_word_byte(&mut row, k, data[i]);
+                i += 1;
+                k += 1;
+            }
+            clip_words(&mut row, width);
+            let _ = output.set_row_words(y, row);
+            y += 1;
+        }
+    }
+    Ok(output)
+}
+
+/// Imports an X11 bitmap, the C source made by [write_xbm] or an image editor, into the top-left corner of a new
+/// [Framebuffer] like [from_pbm]. Only the `_width` and `_height` defines and the array of bytes are read.
+pub const fn from_xbm<const W: usize, const H: usize, const S: usize>(
+    data: &[u8],
+) -> Result<Framebuffer<W, H, S>, ImageError> {
+    let (width, height) = match (find(data, b"_width", 0), find(data, b"_height", 0)) {
+        (Some(w), Some(h)) => match (parse_uint(data, w), parse_uint(data, h)) {
+            (Ok((width, _)), Ok((height, _))) => (width, height),
+            (Err(e), _) | (_, Err(e)) => return Err(e),
+        },
+        _ => return Err(ImageError::UnknownFormat),
+    };
+    if let Err(e) = check_size::<W, H>(width, height) {
+        return Err(e);
+    }
+    let mut i = match find(data, b"{", 0) {
+        Some(i) => i,
+        None => return Err(ImageError::UnknownFormat),
+    };
+
+    let mut output = Framebuffer::new();
+    let row_bytes = width.div_ceil(8);
+    let mut y: usize = 0;
+    while y < height {
+        let mut row = [0u64; S];
+        let mut k: usize = 0;
+        while k < row_bytes {
+            while i < data.len() && (data[i] == b',' || data[i].is_ascii_whitespace()) {
+                i += 1;
+            }
+            if i + 2 >= data.len() {
+                return Err(ImageError::Truncated(i + 3));
+            }
+            if data[i] != b'0' || (data[i + 1] != b'x' && data[i + 1] != b'X') {
+                return Err(ImageError::Malformed(i));
+            }
+            i += 2;
+            let mut byte: u8 = 0;
+            let start = i;
+            while i < data.len() && i < start + 2 {
+                match hex_digit(data[i]) {
+                    Some(digit) => byte = (byte << 4) | digit,
+                    None => break,
+                }
+                i += 1;
+            }
+            if i == start {
+                return Err(ImageError::Malformed(start));
+            }
+            // The leftmost pixel of each byte is its lowest bit.
+            set_word_byte(&mut row, k, byte.reverse_bits());
+            k += 1;
+        }
+        clip_words(&mut row, width);
+        let _ = output.set_row_words(y, row);
+        y += 1;
+    }
+    Ok(output)
+}
+
+/// Exports `display` as a binary (P4) netpbm bitmap. `N` is the length of the file, [p4_len]`(W, H)`, which is
+/// [P4_LEN] for a [DisplayArr], so this does not compile if it is anything else.
+pub fn to_p4<const W: usize, const H: usize, const S: usize, const N: usize>(
+    display: &Framebuffer<W, H, S>,
+) -> [u8; N] {
+    const { assert!(N == p4_len(W, H), "N must be p4_len(W, H)") };
+    let mut output = [0u8; N];
+    output[..3].copy_from_slice(b"P4\n");
+    let i = write_decimal(&mut output, 3, W);
+    output[i] = b' ';
+    let i = write_decimal(&mut output, i + 1, H);
+    output[i] = b'\n';
+    let rows = output[i + 1..].chunks_exact_mut(W.div_ceil(8));
+    for (y, row) in rows.enumerate() {
+        for (idx, byte) in row.iter_mut().enumerate() {
+            // Bytes within the frame are always in bounds.
+            *byte = display.row_byte(y, idx).unwrap_or(0);
+        }
+    }
+    output
+}
+
+/// Exports `display` as a plain (P1) netpbm bitmap, which can be read and edited as text.
+pub fn write_p1<const W: usize, const H: usize, const S: usize, O: Write>(
+    display: &Framebuffer<W, H, S>,
+    output: &mut O,
+) -> core::fmt::Result {
+    writeln!(output, "P1")?;
+    writeln!(output, "{} {}", W, H)?;
+    for y in 0..H {
+        for x in 0..W {
+            // Pixels within the frame are always in bounds.
+            output.write_char(if display.bit(y, x).unwrap_or(false) {
+                '1'
+            } else {
+                '0'
+            })?;
+            if (x + 1) % P1_LINE == 0 || x + 1 == W {
+                output.write_char('\n')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Exports `display` as an X11 bitmap, C source declaring `name_width`, `name_height` and `name_bits`.
+pub fn write_xbm<const W: usize, const H: usize, const S: usize, O: Write>(
+    display: &Framebuffer<W, H, S>,
+    name: &str,
+    output: &mut O,
+) -> core::fmt::Result {
+    writeln!(output, "#define {}_width {}", name, W)?;
+    writeln!(output, "#define {}_height {}", name, H)?;
+    write!(output, "static unsigned char {}_bits[] = {{", name)?;
+    let row_bytes = W.div_ceil(8);
+    let bytes = row_bytes * H;
+    for i in 0..bytes {
+        // Bytes within the frame are always in bounds.
+        let byte = display.row_byte(i / row_bytes, i % row_bytes).unwrap_or(0);
+        if i % XBM_LINE == 0 {
+            write!(output, "\n   ")?;
+        }
+        write!(output, " 0x{:02x}", byte.reverse_bits())?;
+        if i + 1 < bytes {
+            output.write_char(',')?;
+        }
+    }
+    writeln!(output, " }};")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::{draw_circle, DrawMode};
+    use crate::newspeed::strips;
+    use crate::text::draw_text;
+
+    fn screen() -> DisplayArr {
+        let mut display = DisplayArr::new();
+        draw_text(&mut display, b"PBM 123", 1, 1).unwrap();
+        draw_circle(&mut display, 100, 40, 20, DrawMode::Set);
+        display.set_bit(63, 127).unwrap();
+        display
+    }
+
+    fn display_pbm(data: &[u8]) -> Result<DisplayArr, ImageError> {
+        from_pbm(data)
+    }
+
+    fn display_xbm(data: &[u8]) -> Result<DisplayArr, ImageError> {
+        from_xbm(data)
+    }
+
+    #[test]
+    fn round_trips() {
+        let display = screen();
+        assert_eq!(
+            display_pbm(&to_p4::<128, 64, 2, P4_LEN>(&display)).unwrap(),
+            display
+        );
+
+        let mut p1 = String::new();
+        write_p1(&display, &mut p1).unwrap();
+        assert!(p1.starts_with("P1\n128 64\n"));
+        assert!(p1.lines().all(|line| line.len() <= 70));
+        assert_eq!(display_pbm(p1.as_bytes()).unwrap(), display);
+
+        let mut xbm = String::new();
+        write_xbm(&display, "screen", &mut xbm).unwrap();
+        assert!(xbm.starts_with("#define screen_width 128\n#define screen_height 64\n"));
+        assert!(xbm.trim_end().ends_with("0x80 };"));
+        assert_eq!(display_xbm(xbm.as_bytes()).unwrap(), display);
+    }
+
+    #[test]
+    fn small_images() {
+        // Comments and whitespace may appear anywhere in the header and between plain pixels.
+        let plain = b"P1\n# an arrow\n5 3\n00100\n0 1 1 1 0\n11111\n";
+        let display = display_pbm(plain).unwrap();
+        assert_eq!(display.row(0).unwrap(), 0b00100 << 123);
+        assert_eq!(display.row(1).unwrap(), 0b01110 << 123);
+        assert_eq!(display.row(2).unwrap(), 0b11111 << 123);
+        assert_eq!(display.row(3).unwrap(), 0);
+
+        // Padding bits past the width of each row are ignored.
+        let raw = b"P4 5 3 \x27\x77\xff";
+        assert_eq!(display_pbm(raw).unwrap(), display);
+
+        let xbm = b"#define arrow_width 5\n#define arrow_height 3\nstatic char arrow_bits[] = {\n  0x04, 0x0E, 0xff };";
+        assert_eq!(display_xbm(xbm).unwrap(), display);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            display_pbm(b"P2 1 1 0"),
+            Err(ImageError::UnknownFormat)
+        ));
+        assert!(matches!(
+            display_xbm(b"P1 1 1 0"),
+            Err(ImageError::UnknownFormat)
+        ));
+        assert!(matches!(
+            display_pbm(b"P1 129 1"),
+            Err(ImageError::Dimensions(DisplayArrErr::InvalidColumnError(_)))
+        ));
+        assert!(matches!(
+            display_pbm(b"P4 8 65\n"),
+            Err(ImageError::Dimensions(DisplayArrErr::InvalidRowError(_)))
+        ));
+        assert!(matches!(
+            display_pbm(b"P1 2 2 1 0 1"),
+            Err(ImageError::Truncated(13))
+        ));
+        assert!(matches!(
+            display_pbm(b"P1 2 1 1 2"),
+            Err(ImageError::Malformed(9))
+        ));
+        assert!(matches!(
+            display_pbm(b"P1 x 1"),
+            Err(ImageError::Malformed(3))
+        ));
+        assert!(matches!(
+            display_pbm(b"P4 16 2\n\x00\x00"),
+            Err(ImageError::Truncated(12))
+        ));
+        assert!(matches!(
+            display_xbm(b"x_width 8\nx_height 1\n{ 12 };"),
+            Err(ImageError::Malformed(23))
+        ));
+    }
+
+    #[test]
+    fn const_import() {
+        const SPLASH: DisplayArr = match from_pbm(b"P1 3 2\n101\n010") {
+            Ok(display) => display,
+            Err(_) => panic!("splash screen is not a valid PBM"),
+        };
+        assert_eq!(SPLASH.row(0).unwrap(), 0b101 << 125);
+        assert_eq!(SPLASH.row(1).unwrap(), 0b010 << 125);
+    }
+
+    #[test]
+    fn other_sizes() {
+        type Small = Framebuffer<20, 4, { strips(20) }>;
+        let mut small = Small::new();
+        small.set_bit(0, 0).unwrap();
+        small.set_bit(3, 19).unwrap();
+
+        let p4: [u8; p4_len(20, 4)] = to_p4(&small);
+        assert!(p4.starts_with(b"P4\n20 4\n"));
+        assert_eq!(p4.len(), 8 + 3 * 4);
+        assert_eq!(from_pbm::<20, 4, { strips(20) }>(&p4).unwrap(), small);
+
+        let mut p1 = String::new();
+        write_p1(&small, &mut p1).unwrap();
+        assert_eq!(p1.lines().count(), 2 + 4);
+        assert_eq!(
+            from_pbm::<20, 4, { strips(20) }>(p1.as_bytes()).unwrap(),
+            small
+        );
+
+        let mut bits = String::new();
+        write_xbm(&small, "small", &mut bits).unwrap();
+        assert_eq!(
+            from_xbm::<20, 4, { strips(20) }>(bits.as_bytes()).unwrap(),
+            small
+        );
+
+        assert!(matches!(
+            from_pbm::<20, 4, { strips(20) }>(b"P1 21 1"),
+            Err(ImageError::Dimensions(DisplayArrErr::InvalidColumnError(_)))
+        ));
+
+        // Frames wider than a u128 row keep the columns past 127.
+        type Wide = Framebuffer<256, 2, { strips(256) }>;
+        let mut wide = Wide::new();
+        wide.set_bit(1, 200).unwrap();
+        wide.set_bit(0, 255).unwrap();
+        let p4: [u8; p4_len(256, 2)] = to_p4(&wide);
+        assert_eq!(from_pbm::<256, 2, { strips(256) }>(&p4).unwrap(), wide);
+        let mut p1 = String::new();
+        write_p1(&wide, &mut p1).unwrap();
+        assert_eq!(
+            from_pbm::<256, 2, { strips(256) }>(p1.as_bytes()).unwrap(),
+            wide
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use crate::newspeed::DisplayArr;
+use crate::ssd1306::{to_pages, PAGE_HEIGHT, SH1106_OFFSET};
+
+/// Where a chunk of an exported frame goes in controller memory. A driver sets this address, then writes the chunk's
+/// bytes while the controller increments its column address.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChunkAddress {
+    /// Chip select for controllers split over more than one chip, otherwise 0.
+    pub chip: usize,
+    /// Page, or for row-addressed controllers, the row.
+    pub row: usize,
+    /// Column, in the units the controller addresses columns in.
+    pub column: usize,
+}
+
+/// The memory layout of a display controller. Each layout turns a [DisplayArr] into the exact bytes the controller
+/// expects, so changing panels only means changing which layout a driver is given. Layouts can be used as
+/// `&dyn PixelLayout`.
+pub trait PixelLayout {
+    /// Bytes in an exported frame.
+    fn frame_len(&self) -> usize;
+
+    /// Bytes written after each address is set. [Self::frame_len] is always a multiple of this.
+    fn chunk_len(&self) -> usize;
+
+    /// Gets the address chunk `idx` of a frame is written to.
+    fn chunk_address(&self, idx: usize) -> ChunkAddress;
+
+    /// Passes every byte of `display` to `sink`, in the order the controller expects.
+    fn write_frame(&self, display: &DisplayArr, sink: &mut dyn FnMut(u8));
+
+    /// Writes `display` into the start of `out`, returning the number of bytes written. Panics if `out` is shorter
+    /// than [Self::frame_len].
+    fn export(&self, display: &DisplayArr, out: &mut [u8]) -> usize {
+        let mut written: usize = 0;
+        self.write_frame(display, &mut |byte| {
+            out[written] = byte;
+            written += 1;
+        });
+        written
+    }
+}
+
+/// Page count of a [DisplayArr].
+const PAGES: usize = DisplayArr::LEN / PAGE_HEIGHT;
+
+/// SSD1306: 8 pages of 128 columns, each byte 8 rows with the top row in bit 0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Ssd1306;
+
+impl PixelLayout for Ssd1306 {
+    fn frame_len(&self) -> usize {
+        PAGES * DisplayArr::WIDTH
+    }
+
+    fn chunk_len(&self) -> usize {
+        DisplayArr::WIDTH
+    }
+
+    fn chunk_address(&self, idx: usize) -> ChunkAddress {
+        ChunkAddress {
+            chip: 0,
+            row: idx,
+            column: 0,
+        }
+    }
+
+    fn write_frame(&self, display: &DisplayArr, sink: &mut dyn FnMut(u8)) {
+        let pages: [[u8; DisplayArr::WIDTH]; PAGES] = to_pages(display);
+        pages.iter().flatten().for_each(|&byte| sink(byte));
+    }
+}
+
+/// SH1106: laid out like an [Ssd1306], but the panel starts at column [SH1106_OFFSET] of its 132 column memory.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Sh1106;
+
+impl PixelLayout for Sh1106 {
+    fn frame_len(&self) -> usize {
+        Ssd1306.frame_len()
+    }
+
+    fn chunk_len(&self) -> usize {
+        Ssd1306.chunk_len()
+    }
+
+    fn chunk_address(&self, idx: usize) -> ChunkAddress {
+        ChunkAddress {
+            column: SH1106_OFFSET,
+            ..Ssd1306.chunk_address(idx)
+        }
+    }
+
+    fn write_frame(&self, display: &DisplayArr, sink: &mut dyn FnMut(u8)) {
+        Ssd1306.write_frame(display, sink);
+    }
+}
+
+/// UC1701: 8 pages like an [Ssd1306], in a 132 column memory. Modules which mount the panel mirrored (with the
+/// segment direction reversed) see it from column 4 instead of 0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Uc1701 {
+    /// First column of memory which is visible on the panel.
+    pub column_offset: usize,
+}
+
+impl Uc1701 {
+    /// Column offset of a panel mounted with the segment direction reversed.
+    pub const MIRRORED_OFFSET: usize = 4;
+}
+
+impl PixelLayout for Uc1701 {
+    fn frame_len(&self) -> usize {
+        Ssd1306.frame_len()
+    }
+
+    fn chunk_len(&self) -> usize {
+        Ssd1306.chunk_len()
+    }
+
+    fn chunk_address(&self, idx: usize) -> ChunkAddress {
+        ChunkAddress {
+            column: self.column_offset,
+            ..Ssd1306.chunk_address(idx)
+        }
+    }
+
+    fn write_frame(&self, display: &DisplayArr, sink: &mut dyn FnMut(u8)) {
+        Ssd1306.write_frame(display, sink);
+    }
+}
+
+/// KS0108: two chips of 64 columns side by side, each with 8 pages laid out like an [Ssd1306]. The left chip's pages
+/// are written first.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Ks0108;
+
+impl Ks0108 {
+    /// Columns driven by each chip.
+    pub const CHIP_WIDTH: usize = 64;
+}
+
+impl PixelLayout for Ks0108 {
+    fn frame_len(&self) -> usize {
+        PAGES * DisplayArr::WIDTH
+    }
+
+    fn chunk_len(&self) -> usize {
+        Self::CHIP_WIDTH
+    }
+
+    fn chunk_address(&self, idx: usize) -> ChunkAddress {
+        ChunkAddress {
+            chip: idx / PAGES,
+            row: idx % PAGES,
+            column: 0,
+        }
+    }
+
+    fn write_frame(&self, display: &DisplayArr, sink: &mut dyn FnMut(u8)) {
+        let pages: [[u8; DisplayArr::WIDTH]; PAGES] = to_pages(display);
+        for chip in 0..DisplayArr::WIDTH / Self::CHIP_WIDTH {
+            let columns = chip * Self::CHIP_WIDTH..(chip + 1) * Self::CHIP_WIDTH;
+            for page in pages.iter() {
+                page[columns.clone()].iter().for_each(|&byte| sink(byte));
+            }
+        }
+    }
+}
+
+/// ST7920 graphics memory: 16 bit words with the leftmost pixel in the highest bit, sent high byte first. The memory is
+/// 256 columns by 32 rows, so the bottom half of the panel is the right half of each memory row. Each chunk is one
+/// memory row, starting at horizontal word 0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct St7920;
+
+impl St7920 {
+    /// Rows of graphics memory, each holding two rows of the panel.
+    pub const MEMORY_ROWS: usize = DisplayArr::LEN / 2;
+}
+
+impl PixelLayout for St7920 {
+    fn frame_len(&self) -> usize {
+        DisplayArr::LEN * DisplayArr::WIDTH / 8
+    }
+
+    fn chunk_len(&self) -> usize {
+        2 * DisplayArr::WIDTH / 8
+    }
+
+    fn chunk_address(&self, idx: usize) -> ChunkAddress {
+        ChunkAddress {
+            chip: 0,
+            row: idx,
+            column: 0,
+        }
+    }
+
+    fn write_frame(&self, display: &DisplayArr, sink: &mut dyn FnMut(u8)) {
+        for row in 0..Self::MEMORY_ROWS {
+            for half in [row, row + Self::MEMORY_ROWS] {
+                // Rows below MEMORY_ROWS * 2 are always in bounds.
+                let bits = display.row(half).unwrap_or(0);
+                bits.to_be_bytes().iter().for_each(|&byte| sink(byte));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUTS: [&dyn PixelLayout; 5] = [
+        &Ssd1306,
+        &Sh1106,
+        &Uc1701 { column_offset: 0 },
+        &Ks0108,
+        &St7920,
+    ];
+
+    fn corners() -> DisplayArr {
+        let mut display = DisplayArr::new();
+        display.set_bit(0, 0).unwrap();
+        display.set_bit(0, 127).unwrap();
+        display.set_bit(63, 0).unwrap();
+        display.set_bit(63, 127).unwrap();
+        display.set_bit(40, 70).unwrap();
+        display
+    }
+
+    fn exported(layout: &dyn PixelLayout, display: &DisplayArr) -> [u8; 1024] {
+        let mut out = [0u8; 1024];
+        assert_eq!(layout.export(display, &mut out), layout.frame_len());
+        out
+    }
+
+    #[test]
+    fn frame_sizes() {
+        for layout in LAYOUTS {
+            assert_eq!(layout.frame_len(), 1024);
+            assert_eq!(layout.frame_len() % layout.chunk_len(), 0);
+            let lit: u32 = exported(layout, &corners())
+                .iter()
+                .map(|byte| byte.count_ones())
+                .sum();
+            assert_eq!(lit, 5);
+        }
+    }
+
+    #[test]
+    fn page_layouts() {
+        let out = exported(&Ssd1306, &corners());
+        assert_eq!(
+            (out[0], out[127], out[7 * 128], out[1023]),
+            (0x01, 0x01, 0x80, 0x80)
+        );
+        assert_eq!(out[5 * 128 + 70], 0x01);
+        assert_eq!(exported(&Sh1106, &corners()), out);
+        assert_eq!(
+            Sh1106.chunk_address(3),
+            ChunkAddress {
+                chip: 0,
+                row: 3,
+                column: 2
+            }
+        );
+
+        let mirrored = Uc1701 {
+            column_offset: Uc1701::MIRRORED_OFFSET,
+        };
+        assert_eq!(exported(&mirrored, &corners()), out);
+        assert_eq!(mirrored.chunk_address(7).column, 4);
+    }
+
+    #[test]
+    fn ks0108_layout() {
+        let out = exported(&Ks0108, &corners());
+        // Left chip: page 0 column 0, page 7 column 0.
+        assert_eq!((out[0], out[7 * 64]), (0x01, 0x80));
+        // Right chip starts after the left chip's 8 pages.
+        assert_eq!((out[512 + 63], out[512 + 7 * 64 + 63]), (0x01, 0x80));
+        assert_eq!(out[512 + 5 * 64 + 6], 0x01);
+        assert_eq!(
+            Ks0108.chunk_address(9),
+            ChunkAddress {
+                chip: 1,
+                row: 1,
+                column: 0
+            }
+        );
+    }
+
+    #[test]
+    fn st7920_layout() {
+        let out = exported(&St7920, &corners());
+        // Memory row 0 holds panel row 0, then panel row 32.
+        assert_eq!((out[0], out[15]), (0x80, 0x01));
+        assert_eq!(out[16..32], [0; 16]);
+        // Memory row 31 holds panel rows 31 and 63.
+        assert_eq!((out[31 * 32 + 16], out[31 * 32 + 31]), (0x80, 0x01));
+        // Panel row 40 is in the right half of memory row 8, column 70 in its ninth byte.
+        assert_eq!(out[8 * 32 + 16 + 8], 0x02);
+        assert_eq!(St7920.chunk_address(31).row, 31);
+    }
+}
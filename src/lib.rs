@@ -1,11 +1,27 @@
 #![feature(portable_simd)]
 #![feature(core_intrinsics)]
 #![feature(const_eval_select)]
-#![feature(lazy_cell)]
 #![feature(test)]
-////#![no_std]
+#![allow(internal_features)]
+// #![no_std]
 
 extern crate test;
 
+pub mod compress;
+pub mod dirty;
+pub mod draw;
+pub mod font;
+pub mod image;
+pub mod layout;
 pub mod newspeed;
-pub mod oldspeed;
\ No newline at end of file
+pub mod oldspeed;
+pub mod png;
+pub mod segment;
+#[cfg(test)]
+mod snapshot;
+pub mod sprite;
+pub mod ssd1306;
+pub mod swap;
+pub mod term;
+pub mod text;
+pub mod viewport;
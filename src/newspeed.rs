@@ -4,8 +4,8 @@ use core::result::Result;
 use core::result::Result::Err;
 use core::result::Result::Ok;
 use core::simd::prelude::*;
-use core::unreachable;
 use core::write;
+use core::writeln;
 
 pub struct DisplayArrIndexError {
     expected: usize,
@@ -18,6 +18,11 @@ pub enum DisplayArrErr {
     InvalidRowError(DisplayArrIndexError),
     /// Index out of bounds error for a column. Argument 0 is expected maximum length, and argument 1 is actual length.
     InvalidColumnError(DisplayArrIndexError),
+    /// Text does not fit in the space it was laid out in. Argument 0 is the last available column, and argument 1 is the
+    /// last column the text would need.
+    TextOverflowError(DisplayArrIndexError),
+    /// A rectangle which must cover at least one pixel, such as the bounds of a viewport, has no width or no height.
+    EmptyRectError(Rect),
 }
 
 impl DisplayArrErr {
@@ -35,13 +40,20 @@ impl DisplayArrErr {
     }
 
     /// Invalid row index error helper
-    #[allow(non_upper_case_globals)]
-    const row_err: fn(usize, usize) -> Self =
-        |expected, actual| Self::idx_error::<true>(expected, actual);
+    pub(crate) const fn row_err(expected: usize, actual: usize) -> Self {
+        Self::idx_error::<true>(expected, actual)
+    }
     /// Invalid column index error helper
-    #[allow(non_upper_case_globals)]
-    const column_err: fn(usize, usize) -> Self =
-        |expected, actual| Self::idx_error::<false>(expected, actual);
+    pub(crate) const fn column_err(expected: usize, actual: usize) -> Self {
+        Self::idx_error::<false>(expected, actual)
+    }
+    /// Text overflow error helper
+    pub(crate) const fn text_overflow_err(expected: usize, actual: usize) -> Self {
+        if actual <= expected {
+            panic!("threw a text overflow error where the text fit!");
+        }
+        Self::TextOverflowError(DisplayArrIndexError { expected, actual })
+    }
 }
 
 impl core::fmt::Display for DisplayArrErr {
@@ -53,6 +65,16 @@ impl core::fmt::Display for DisplayArrErr {
             DisplayArrErr::InvalidColumnError(DisplayArrIndexError { expected, actual }) => {
                 (expected, actual, "column")
             }
+            DisplayArrErr::TextOverflowError(DisplayArrIndexError { expected, actual }) => {
+                return write!(
+                    f,
+                    "Text does not fit: expected to end by column {:}, but needs column {:}",
+                    expected, actual
+                );
+            }
+            DisplayArrErr::EmptyRectError(rect) => {
+                return write!(f, "Empty rectangle provided: {:?}", rect);
+            }
         };
         write!(
             f,
@@ -68,23 +90,130 @@ impl core::fmt::Debug for DisplayArrErr {
     }
 }
 
+/// A rectangle of pixels, where `x` is the leftmost column and `y` is the top row.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Column just past the right edge of `self`.
+    pub const fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// Row just past the bottom edge of `self`.
+    pub const fn bottom(&self) -> usize {
+        self.y + self.height
+    }
 
-#[derive(Clone, Copy)]
-pub struct DisplayArr {
-    left: u64x64,
-    right: u64x64,
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
 }
 
-impl PartialEq for DisplayArr {
-    fn eq(&self, other: &Self) -> bool {
-        (self.left == other.left) && (self.right == other.right)
+/// Gets the lane paired with each lane in the transpose stage which swaps blocks of `j` bits.
+const fn transpose_partners(j: usize) -> [usize; 64] {
+    let mut output = [0usize; 64];
+    let mut i: usize = 0;
+    while i < 64 {
+        output[i] = i ^ j;
+        i += 1;
     }
+    output
 }
 
-impl Eq for DisplayArr {}
+/// Gets whether each lane is the upper (lower index) lane of its pair in the transpose stage for `j`.
+const fn transpose_upper_lanes(j: usize) -> [bool; 64] {
+    let mut output = [false; 64];
+    let mut i: usize = 0;
+    while i < 64 {
+        output[i] = i & j == 0;
+        i += 1;
+    }
+    output
+}
 
-impl DisplayArr {
-    pub const LEN: usize = 64;
+/// Transposes a 64x64 bit matrix, where lane `i` is row `i` and bit 63 is column 0. After transposing, lane `i` holds
+/// column `i`, with row 0 in bit 63. Works in six stages, each swapping off-diagonal blocks half the size of the last
+/// between every pair of lanes at once.
+pub fn transpose_u64x64(matrix: u64x64) -> u64x64 {
+    macro_rules! stage {
+        ($matrix:expr, $j:literal, $mask:literal) => {{
+            const PARTNERS: [usize; 64] = transpose_partners($j);
+            let partner = simd_swizzle!($matrix, PARTNERS);
+            let mask = u64x64::splat($mask);
+            let shift = u64x64::splat($j);
+            let upper = ($matrix ^ (partner >> shift)) & mask;
+            let lower = ((partner ^ ($matrix >> shift)) & mask) << shift;
+            let is_upper = mask64x64::from_array(transpose_upper_lanes($j));
+            $matrix ^ is_upper.select(upper, lower)
+        }};
+    }
+
+    let matrix = stage!(matrix, 32, 0x0000_0000_ffff_ffff);
+    let matrix = stage!(matrix, 16, 0x0000_ffff_0000_ffff);
+    let matrix = stage!(matrix, 8, 0x00ff_00ff_00ff_00ff);
+    let matrix = stage!(matrix, 4, 0x0f0f_0f0f_0f0f_0f0f);
+    let matrix = stage!(matrix, 2, 0x3333_3333_3333_3333);
+    stage!(matrix, 1, 0x5555_5555_5555_5555)
+}
+
+/// Gets the number of 64 column strips a [Framebuffer] `width` columns wide is stored in.
+pub const fn strips(width: usize) -> usize {
+    width.div_ceil(64)
+}
+
+/// A `W` column by `H` row framebuffer, for panels such as 128x32, 128x64, 256x64 or 96x16. Columns are stored in
+/// `S` strips of 64, each a vector with one lane per row, so whole-frame operations work on every row at once. Within
+/// a strip, the leftmost column is the highest bit. Columns past `W` are always kept clear.
+///
+/// `S` must be [strips]`(W)`, and `H` can be at most 64, since a column is a [u64]. Both are checked when the
+/// framebuffer is created, so `Framebuffer<256, 64, { strips(256) }>` compiles but `Framebuffer<256, 64, 2>::new()`
+/// does not.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Framebuffer<const W: usize, const H: usize, const S: usize> {
+    strips: [Simd<u64, H>; S],
+}
+
+/// A 128x64 [Framebuffer], the size of the speedometer display.
+pub type DisplayArr = Framebuffer<128, 64, { strips(128) }>;
+
+impl<const W: usize, const H: usize, const S: usize> Default for Framebuffer<W, H, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize, const S: usize> Framebuffer<W, H, S> {
+    pub const LEN: usize = H;
+    pub const WIDTH: usize = W;
+    /// Number of 64 column strips each row is stored in.
+    pub const STRIPS: usize = S;
+    /// Widest row which [u128] rows and masks can hold.
+    const ROW_WIDTH: usize = if W < 128 { W } else { 128 };
+    /// Fails to compile if `S` or `H` do not fit `W` by `H` pixels. See [Framebuffer].
+    const SIZE_CHECK: () = {
+        assert!(S == strips(W), "S must be strips(W)");
+        assert!(H <= 64, "framebuffers can be at most 64 rows tall");
+    };
+    /// Fails to compile if rows of `self` do not fit in a [u128].
+    const U128_ROWS: () = assert!(
+        W <= 128,
+        "framebuffers wider than 128 columns do not have u128 rows; use the row_words methods"
+    );
 
     pub const fn len() -> usize {
         Self::LEN
@@ -95,7 +224,7 @@ impl DisplayArr {
     /// Inverse of [combine_u128].
     #[inline]
     const fn split_u128(num: u128) -> (u64, u64) {
-        ((num << 64) as u64, num as u64)
+        ((num >> 64) as u64, num as u64)
     }
 
     /// Combines two [u64]s into a [u128], where the first [u64] is the upper 64 bits and the second [u64] is the lower 64 bits.
@@ -106,164 +235,444 @@ impl DisplayArr {
         ((a as u128) << 64) | b as u128
     }
 
-    /// Gets bit `idx` from `self`, where 0 is the highest (leftmost) bit. Returns [DisplayArrErr::InvalidColumnError] if idx is out of bounds.
-    const fn get_bit(number: u64, position: usize) -> Result<u64, DisplayArrErr> {
-        if position > 63 {
-            return Err(DisplayArrErr::InvalidColumnError(DisplayArrIndexError {
-                expected: 63,
-                actual: position,
-            }));
+    /// Bits of strip `idx` which hold columns within the framebuffer.
+    const fn strip_mask(idx: usize) -> u64 {
+        let end = (idx + 1) * 64;
+        if end <= W {
+            u64::MAX
+        } else if idx * 64 >= W {
+            0
+        } else {
+            u64::MAX << (end - W)
         }
-        let mask = 1u64 << position;
-        let output = (number & mask) >> position;
-        if !((output == 0) || (output == 1)) {
-            unsafe {
-                core::hint::unreachable_unchecked();
-            }
+    }
+
+    /// Creates a new [Framebuffer] where every row of every strip is `value`, within the framebuffer's columns.
+    const fn filled(value: u64) -> Self {
+        let () = Self::SIZE_CHECK;
+        let mut strips = [Simd::from_array([0; H]); S];
+        let mut i: usize = 0;
+        while i < Self::STRIPS {
+            strips[i] = Simd::from_array([value & Self::strip_mask(i); H]);
+            i += 1;
+        }
+        Self { strips }
+    }
+    /// Creates a new zeroed [Framebuffer].
+    pub const fn new() -> Self {
+        Self::filled(0)
+    }
+    /// Creates a new [Framebuffer] where are all pixels are 1.
+    pub const fn new_full() -> Self {
+        Self::filled(u64::MAX)
+    }
+
+    /// Construct a new [Framebuffer] from an array of rows, in the same form as [Self::row]. Does not compile if `W` is
+    /// more than 128.
+    pub const fn new_from_array(array: [u128; H]) -> Self {
+        let () = Self::U128_ROWS;
+        let mut output = Self::new();
+        let mut i: usize = 0;
+        while i < H {
+            let _ = output.set_row(i, array[i]);
+            i += 1;
+        }
+        output
+    }
+
+    /// Gets row `idx` from `self`, where 0 is the top row and column 0 is the highest bit. Returns
+    /// [DisplayArrErr::InvalidRowError] if idx is out of bounds. Does not compile if `W` is more than 128.
+    pub const fn row(&self, idx: usize) -> Result<u128, DisplayArrErr> {
+        let () = Self::U128_ROWS;
+        if idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, idx));
+        }
+        let left = self.strips[0].as_array()[idx];
+        let right = if Self::STRIPS > 1 {
+            self.strips[1].as_array()[idx]
+        } else {
+            0
+        };
+        Ok(Self::combine_u128(left, right))
+    }
+
+    /// Sets row `idx` in `self`. Columns past the right edge are ignored. Returns [DisplayArrErr::InvalidRowError] if
+    /// `idx` is out of bounds. Does not compile if `W` is more than 128.
+    pub const fn set_row(&mut self, idx: usize, src: u128) -> Result<(), DisplayArrErr> {
+        let () = Self::U128_ROWS;
+        if idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, idx));
+        }
+        let (left, right) = Self::split_u128(src);
+        self.strips[0].as_mut_array()[idx] = left & Self::strip_mask(0);
+        if Self::STRIPS > 1 {
+            self.strips[1].as_mut_array()[idx] = right & Self::strip_mask(1);
+        }
+        Ok(())
+    }
+
+    /// Preforms a bitwise or-assignment to row `idx` of `self`. Columns past the right edge are ignored. Returns
+    /// [DisplayArrErr::InvalidRowError] if `idx` is out of bounds. Does not compile if `W` is more than 128.
+    pub const fn oreq_row(&mut self, idx: usize, src: u128) -> Result<(), DisplayArrErr> {
+        let () = Self::U128_ROWS;
+        if idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, idx));
+        }
+        let (left, right) = Self::split_u128(src);
+        self.strips[0].as_mut_array()[idx] |= left & Self::strip_mask(0);
+        if Self::STRIPS > 1 {
+            self.strips[1].as_mut_array()[idx] |= right & Self::strip_mask(1);
+        }
+        Ok(())
+    }
+
+    /// Gets row `idx` from `self` as one [u64] per strip, for framebuffers of any width. Returns
+    /// [DisplayArrErr::InvalidRowError] if idx is out of bounds.
+    pub const fn row_words(&self, idx: usize) -> Result<[u64; S], DisplayArrErr> {
+        if idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, idx));
+        }
+        let mut output = [0u64; S];
+        let mut i: usize = 0;
+        while i < Self::STRIPS {
+            output[i] = self.strips[i].as_array()[idx];
+            i += 1;
         }
         Ok(output)
     }
 
-    /// Creates a new [DisplayArr], where all left rows are the `left` parameter and all right rows are the `right` parameter
-    pub const fn splat(left: u64, right: u64) -> Self {
-        /// Fast splat at runtime
-        fn simd_splat(left: u64, right: u64) -> DisplayArr {
-            DisplayArr {
-                left: u64x64::splat(left),
-                right: u64x64::splat(right),
-            }
+    /// Sets row `idx` in `self` from one [u64] per strip. Columns past the right edge are ignored. Returns
+    /// [DisplayArrErr::InvalidRowError] if `idx` is out of bounds.
+    pub const fn set_row_words(&mut self, idx: usize, src: [u64; S]) -> Result<(), DisplayArrErr> {
+        if idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, idx));
+        }
+        let mut i: usize = 0;
+        while i < Self::STRIPS {
+            self.strips[i].as_mut_array()[idx] = src[i] & Self::strip_mask(i);
+            i += 1;
         }
+        Ok(())
+    }
 
-        /// Allow splat at compile-time
-        #[inline(always)]
-        const fn const_splat(left: u64, right: u64) -> DisplayArr {
-            DisplayArr {
-                left: u64x64::from_array([left; 64]),
-                right: u64x64::from_array([right; 64]),
-            }
+    /// Gets byte `idx` of row `row_idx`, which holds columns `8 * idx` to `8 * idx + 7` with the leftmost in the highest
+    /// bit, the order rows are stored in by bitmap formats such as PBM and PNG. Columns past the right edge are clear.
+    /// Returns [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError] if either index is out of
+    /// bounds.
+    pub const fn row_byte(&self, row_idx: usize, idx: usize) -> Result<u8, DisplayArrErr> {
+        if row_idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, row_idx));
+        }
+        if idx * 8 >= W {
+            return Err(DisplayArrErr::column_err(W - 1, idx * 8));
         }
+        Ok((self.strips[idx / 8].as_array()[row_idx] >> (56 - 8 * (idx % 8))) as u8)
+    }
 
-        unsafe { const_eval_select((left, right), const_splat, simd_splat) } // trollface
+    /// Gets the strip holding column `idx`, and the bit of that column within each row of the strip. Returns
+    /// [DisplayArrErr::InvalidColumnError] if `idx` is out of bounds.
+    fn column_strip(
+        &mut self,
+        idx: usize,
+    ) -> Result<(&mut Simd<u64, H>, Simd<u64, H>), DisplayArrErr> {
+        if idx >= W {
+            return Err(DisplayArrErr::column_err(W - 1, idx));
+        }
+        Ok((
+            &mut self.strips[idx / 64],
+            Simd::splat(1 << (63 - idx % 64)),
+        ))
     }
-    /// Creates a new zeroed [DisplayArr].
-    pub const fn new() -> Self {
-        Self::splat(0, 0)
+
+    /// Gets column `idx` from `self`, where 0 is the highest (leftmost) column. Row `i` is bit `63 - i` of the output,
+    /// so row 0 is the highest bit, and rows past `H` leave the lowest bits clear. This is the reverse of the order
+    /// columns were first returned in, where row `i` was bit `i`; [u64::reverse_bits] converts between the two.
+    /// Returns [DisplayArrErr::InvalidColumnError] if idx is out of bounds.
+    pub fn column(&self, idx: usize) -> Result<u64, DisplayArrErr> {
+        if idx >= W {
+            return Err(DisplayArrErr::column_err(W - 1, idx));
+        }
+        let bit = Simd::splat(1 << (63 - idx % 64));
+        Ok((self.strips[idx / 64] & bit)
+            .simd_ne(Simd::splat(0))
+            .to_bitmask()
+            .reverse_bits())
     }
-    /// Creates a new [DisplayArr] where are all pixels are 1.
-    pub const fn new_full() -> Self {
-        Self::splat(u64::MAX, u64::MAX)
+
+    /// Gets every column of `self` at once, in the same form as [Self::column], so row 0 is the highest bit of each.
+    /// Each strip is transposed as a whole, which is much cheaper than a call to [Self::column] per column.
+    pub fn columns(&self) -> [u64; W] {
+        let mut output = [0u64; W];
+        for (i, strip) in self.strips.iter().enumerate() {
+            let mut lanes = [0u64; 64];
+            lanes[..H].copy_from_slice(strip.as_array());
+            let columns = transpose_u64x64(u64x64::from_array(lanes));
+            let start = i * 64;
+            let end = if start + 64 < W { start + 64 } else { W };
+            output[start..end].copy_from_slice(&columns.as_array()[..end - start]);
+        }
+        output
     }
 
-    /// Construct a new [DisplayArr] from two halves.
-    pub fn new_from_halves(left: u64x64, right: u64x64) -> Self {
-        Self {
-            left: left,
-            right: right,
+    /// Construct a new [Framebuffer] from columns in the same form as [Self::column]. Inverse of [Self::columns].
+    pub fn new_from_columns(columns: [u64; W]) -> Self {
+        let mut output = Self::new();
+        for (i, strip) in output.strips.iter_mut().enumerate() {
+            let mut lanes = [0u64; 64];
+            let start = i * 64;
+            let end = if start + 64 < W { start + 64 } else { W };
+            lanes[..end - start].copy_from_slice(&columns[start..end]);
+            let rows = transpose_u64x64(u64x64::from_array(lanes));
+            *strip = Simd::from_slice(&rows.as_array()[..H]);
         }
+        output
     }
-    /// Construct a new [DisplayArr] from an array of the same shape.
-    pub fn new_from_array(array: [u128; 64]) -> Self {
-        let mut leftarr = [0u64; 64];
-        let mut rightarr = [0u64; 64];
-        let mut i: usize = 0;
 
-        while i < 64 {
-            (leftarr[i], rightarr[i]) = Self::split_u128(array[i]);
-            i += 1;
+    /// Gets which rows of `self` have any pixel set, in the same form as [Self::column].
+    pub fn occupied_rows(&self) -> u64 {
+        let mut occupied = Mask::<i64, H>::splat(false);
+        for strip in self.strips.iter() {
+            occupied |= strip.simd_ne(Simd::splat(0));
         }
-        Self::new_from_halves(u64x64::from_array(leftarr), u64x64::from_array(rightarr))
+        occupied.to_bitmask().reverse_bits()
     }
 
-    /// Get row-half `x` from `self`, where the returned value is the upper (left) half if y = true, else the returned value us the lower (right) half. Returns [DisplayArrErr::InvalidRowError] if idx is out of bounds.
-    fn row_half(&self, x: usize, y: bool) -> Result<u64, DisplayArrErr> {
-        return match x {
-            0..=63 => Ok(if !y { self.left[x] } else { self.right[x] }),
-            _ => Err(DisplayArrErr::row_err(63, x)),
-        };
+    /// Gets the bitwise or of every row set in `rows`, where row 0 is the highest bit, in the same form as
+    /// [Self::row_words].
+    pub fn union_rows(&self, rows: u64) -> [u64; S] {
+        let selected = Mask::<i64, H>::from_bitmask(rows.reverse_bits());
+        let mut output = [0u64; S];
+        for (word, strip) in output.iter_mut().zip(self.strips.iter()) {
+            *word = selected.select(*strip, Simd::splat(0)).reduce_or();
+        }
+        output
     }
 
-    /// Gets row `idx` from `self`, where 0 is the top row. Returns [DisplayArrErr::InvalidRowError] if idx is out of bounds.
-    pub fn row(&self, idx: usize) -> Result<u128, DisplayArrErr> {
-        return match idx {
-            0..=63 => Ok(Self::combine_u128(self.left[idx], self.right[idx])),
-            _ => Err(DisplayArrErr::row_err(63, idx)),
+    /// Sets column `idx` in `self`, where row `i` is bit `63 - i` of `src` as in [Self::column], so row 0 is the
+    /// highest bit. Bits for rows past `H` are ignored. Returns [DisplayArrErr::InvalidColumnError] if `idx` is out of
+    /// bounds.
+    pub fn set_column(&mut self, idx: usize, src: u64) -> Result<(), DisplayArrErr> {
+        let (strip, bit) = self.column_strip(idx)?;
+        let rows = Mask::<i64, H>::from_bitmask(src.reverse_bits());
+        *strip = rows.select(*strip | bit, *strip & !bit);
+        Ok(())
+    }
+
+    /// Preforms a bitwise or-assignment to column `idx` of `self`, in the same bit order as [Self::set_column]. Returns
+    /// [DisplayArrErr::InvalidColumnError] if `idx` is out of bounds.
+    pub fn oreq_column(&mut self, idx: usize, src: u64) -> Result<(), DisplayArrErr> {
+        let (strip, bit) = self.column_strip(idx)?;
+        let rows = Mask::<i64, H>::from_bitmask(src.reverse_bits());
+        *strip |= rows.select(bit, Simd::splat(0));
+        Ok(())
+    }
+
+    /// Gets a row mask with columns `x..x + width` set, where column 0 is the highest (leftmost) bit.
+    /// Columns past the right edge are ignored.
+    pub const fn row_mask(x: usize, width: usize) -> u128 {
+        if width == 0 || x >= Self::ROW_WIDTH {
+            return 0;
+        }
+        let end = if x + width < Self::ROW_WIDTH {
+            x + width
+        } else {
+            Self::ROW_WIDTH
         };
+        let upper = u128::MAX >> x;
+        if end == 128 {
+            upper
+        } else {
+            upper & !(u128::MAX >> end)
+        }
     }
-    /// Gets column `idx` from `self`, where 0 is the highest (leftmost) column. Returns [DisplayArrErr::InvalidColumnError] if idx is out of bounds.
-    pub fn column(&self, idx: usize) -> Result<u64, DisplayArrErr> {
-        // TODO: can probably get this from a shifted mask?
 
-        let mut output: u64 = 0;
-        match idx {
-            0..=63 => {
-                for i in 0..64 {
-                    output |= Self::get_bit(self.left[i], idx)? << i;
-                }
-                Ok(output)
-            }
+    /// Checks that `rect` lies entirely within `self`. Returns [DisplayArrErr::InvalidColumnError] or
+    /// [DisplayArrErr::InvalidRowError] for the first edge which does not.
+    pub const fn check_rect(rect: &Rect) -> Result<(), DisplayArrErr> {
+        if rect.right() > Self::WIDTH {
+            return Err(DisplayArrErr::column_err(Self::WIDTH - 1, rect.right() - 1));
+        }
+        if rect.bottom() > Self::LEN {
+            return Err(DisplayArrErr::row_err(Self::LEN - 1, rect.bottom() - 1));
+        }
+        Ok(())
+    }
 
-            64..=127 => {
-                for i in 0..64 {
-                    output |= Self::get_bit(self.right[i], idx - 64)? << i;
-                }
-                Ok(output)
-            }
+    /// Gets the strip and mask within that strip of a single pixel, checking the row before the column.
+    const fn pixel_mask(row_idx: usize, column_idx: usize) -> Result<(usize, u64), DisplayArrErr> {
+        if row_idx >= H {
+            return Err(DisplayArrErr::row_err(H - 1, row_idx));
+        }
+        if column_idx >= W {
+            return Err(DisplayArrErr::column_err(W - 1, column_idx));
+        }
+        Ok((column_idx / 64, 1u64 << (63 - column_idx % 64)))
+    }
 
-            _ => return Err(DisplayArrErr::column_err(127, idx)),
+    /// Gets a single boolean bit from `self`, where column 0 is the leftmost, which is the highest bit of [Self::row].
+    /// Returns [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError] if either index is out of
+    /// bounds.
+    pub const fn bit(&self, row_idx: usize, column_idx: usize) -> Result<bool, DisplayArrErr> {
+        match Self::pixel_mask(row_idx, column_idx) {
+            Ok((strip, mask)) => Ok(self.strips[strip].as_array()[row_idx] & mask != 0),
+            Err(e) => Err(e),
         }
     }
 
-    /// Sets row `idx` in `self`. Returns [DisplayArrErr::InvalidRowError] if `idx` is out of bounds.
-    pub fn set_row(&mut self, idx: usize, src: u128) -> Result<(), DisplayArrErr> {
-        return match idx {
-            0..=63 => {
-                (self.left[idx], self.right[idx]) = Self::split_u128(src);
+    /// Gets a mutable reference to the part of row `row_idx` holding the pixel, and that pixel's mask.
+    const fn pixel_mut(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(&mut u64, u64), DisplayArrErr> {
+        match Self::pixel_mask(row_idx, column_idx) {
+            Ok((strip, mask)) => Ok((&mut self.strips[strip].as_mut_array()[row_idx], mask)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Turns on a single pixel. Returns [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError] if
+    /// either index is out of bounds.
+    pub const fn set_bit(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(), DisplayArrErr> {
+        match self.pixel_mut(row_idx, column_idx) {
+            Ok((word, mask)) => {
+                *word |= mask;
                 Ok(())
             }
-            _ => Err(DisplayArrErr::row_err(63, idx)),
-        };
+            Err(e) => Err(e),
+        }
     }
 
-    /// Preforms a bitwise or-assignment to `self`.
-    pub fn oreq_row(&mut self, idx: usize, src: u128) -> Result<(), DisplayArrErr> {
-        return match idx {
-            0..=63 => {
-                let (left, right) = Self::split_u128(src);
-                self.left[idx] |= left;
-                self.right[idx] |= right;
+    /// Turns off a single pixel. Returns [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError] if
+    /// either index is out of bounds.
+    pub const fn clear_bit(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(), DisplayArrErr> {
+        match self.pixel_mut(row_idx, column_idx) {
+            Ok((word, mask)) => {
+                *word &= !mask;
                 Ok(())
             }
-
-            _ => Err(DisplayArrErr::row_err(63, idx)),
-        };
+            Err(e) => Err(e),
+        }
     }
 
-    /// Gets a single boolean bit from `self`.
-    pub fn bit(&self, row_idx: usize, column_idx: usize) -> Result<bool, DisplayArrErr> {
-        let (column_idx_adj, half) = if column_idx > 63 {
-            (column_idx - 64, true)
-        } else {
-            (column_idx, false)
-        };
-        let row_val: u64 = self.row_half(row_idx, half)?;
+    /// Inverts a single pixel. Returns [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError] if
+    /// either index is out of bounds.
+    pub const fn toggle_bit(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(), DisplayArrErr> {
+        match self.pixel_mut(row_idx, column_idx) {
+            Ok((word, mask)) => {
+                *word ^= mask;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
 
-        Ok(Self::get_bit(row_val, column_idx_adj)? != 0)
+    /// Turns on every `(row_idx, column_idx)` pixel from `pixels`. The pixels are gathered into row masks first and
+    /// written with one or-assignment per strip, so if any index is out of bounds, its
+    /// [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError] is returned and `self` is unchanged.
+    pub fn set_pixels<I: IntoIterator<Item = (usize, usize)>>(
+        &mut self,
+        pixels: I,
+    ) -> Result<(), DisplayArrErr> {
+        let mut masks = [[0u64; H]; S];
+        for (row_idx, column_idx) in pixels {
+            let (strip, mask) = Self::pixel_mask(row_idx, column_idx)?;
+            masks[strip][row_idx] |= mask;
+        }
+        for (strip, mask) in self.strips.iter_mut().zip(masks) {
+            *strip |= Simd::from_array(mask);
+        }
+        Ok(())
     }
 
     /// Rotates rows down by a constant amount. It is preferable to have a single call to this with a value `N` determined
     /// at compile time, as this operation can be expensive if done in a loop.
     /// For example, `for _ in 0..4 {x.rotate_rows_down<1>()}` is ***much*** slower than `x.rotate_rows_down<4>()`.
     pub fn rotate_rows_down<const N: usize>(&mut self) {
-        self.left = self.left.rotate_lanes_right::<N>();
-        self.right = self.right.rotate_lanes_right::<N>();
+        for strip in self.strips.iter_mut() {
+            *strip = strip.rotate_elements_right::<N>();
+        }
     }
     /// Rotates rows up. It is preferable to have a single call to this with a value `N` determined
     /// at compile time, as this operation can be expensive if done in a loop.
     /// For example, `for _ in 0..4 {x.rotate_rows_up<1>()}` is ***much*** slower than `x.rotate_rows_up<4>()`.
     pub fn rotate_rows_up<const N: usize>(&mut self) {
-        self.left = self.left.rotate_lanes_left::<N>();
-        self.right = self.right.rotate_lanes_left::<N>();
+        for strip in self.strips.iter_mut() {
+            *strip = strip.rotate_elements_left::<N>();
+        }
+    }
+
+    /// Mirrors `self` left to right, by reversing the bits of every row and the order of the strips. Framebuffers
+    /// which are not a multiple of 64 columns wide are then shifted back over their clear columns.
+    pub fn flip_horizontal(&mut self) {
+        self.strips.reverse();
+        for strip in self.strips.iter_mut() {
+            *strip = strip.reverse_bits();
+        }
+        *self <<= Self::STRIPS * 64 - W;
+    }
+    /// Mirrors `self` top to bottom, by reversing the order of the rows.
+    pub fn flip_vertical(&mut self) {
+        for strip in self.strips.iter_mut() {
+            *strip = strip.reverse();
+        }
+    }
+    /// Rotates `self` by 180 degrees, for a display mounted upside down.
+    pub fn rotate_180(&mut self) {
+        self.flip_horizontal();
+        self.flip_vertical();
+    }
+}
+
+impl DisplayArr {
+    /// Creates a new [DisplayArr], where all left rows are the `left` parameter and all right rows are the `right` parameter
+    pub const fn splat(left: u64, right: u64) -> Self {
+        /// Fast splat at runtime
+        fn simd_splat(left: u64, right: u64) -> DisplayArr {
+            DisplayArr {
+                strips: [u64x64::splat(left), u64x64::splat(right)],
+            }
+        }
+
+        /// Allow splat at compile-time
+        #[inline(always)]
+        const fn const_splat(left: u64, right: u64) -> DisplayArr {
+            DisplayArr {
+                strips: [
+                    u64x64::from_array([left; 64]),
+                    u64x64::from_array([right; 64]),
+                ],
+            }
+        }
+
+        const_eval_select((left, right), const_splat, simd_splat) // trollface
+    }
+
+    /// Construct a new [DisplayArr] from two halves.
+    pub fn new_from_halves(left: u64x64, right: u64x64) -> Self {
+        Self {
+            strips: [left, right],
+        }
+    }
+    /// Gets mutable references to the left (columns 0..64) and right (columns 64..128) halves of `self`, for
+    /// operations which work on whole halves at once.
+    pub(crate) fn halves_mut(&mut self) -> (&mut u64x64, &mut u64x64) {
+        let [left, right] = &mut self.strips;
+        (left, right)
     }
+
     /// Rotates columns right. This is an expensive operation, so use sparingly.
     pub fn rotate_rows_right(&mut self, idx: u32) {
         for i in 0..=63 {
@@ -278,166 +687,281 @@ impl DisplayArr {
                 .unwrap();
         }
     }
+
+    /// Rotates `self` 90 degrees clockwise into a [PortraitArr], so the left column becomes the top row.
+    pub fn rotate_90_cw(&self) -> PortraitArr {
+        // Transposed lanes hold columns with row 0 in the highest bit, which must end up on the right.
+        PortraitArr {
+            top: transpose_u64x64(self.strips[0]).reverse_bits(),
+            bottom: transpose_u64x64(self.strips[1]).reverse_bits(),
+        }
+    }
+    /// Rotates `self` 90 degrees counter-clockwise into a [PortraitArr], so the right column becomes the top row.
+    pub fn rotate_90_ccw(&self) -> PortraitArr {
+        PortraitArr {
+            top: transpose_u64x64(self.strips[1]).reverse(),
+            bottom: transpose_u64x64(self.strips[0]).reverse(),
+        }
+    }
 }
 
-impl core::ops::BitAnd for DisplayArr {
-    type Output = Self;
-    fn bitand(self, rhs: Self) -> Self::Output {
+/// A 64 column by 128 row portrait framebuffer, made by rotating a [DisplayArr] by 90 degrees. `top` holds rows 0..64
+/// and `bottom` holds rows 64..128, with column 0 in the highest bit of each row.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PortraitArr {
+    top: u64x64,
+    bottom: u64x64,
+}
+
+impl Default for PortraitArr {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PortraitArr {
+    pub const LEN: usize = 128;
+    pub const WIDTH: usize = 64;
+
+    /// Creates a new zeroed [PortraitArr].
+    pub const fn new() -> Self {
         Self {
-            left: self.left & rhs.left,
-            right: self.right & rhs.right,
+            top: u64x64::from_array([0; 64]),
+            bottom: u64x64::from_array([0; 64]),
         }
     }
+
+    /// Gets row `idx` from `self`, where 0 is the top row. Returns [DisplayArrErr::InvalidRowError] if idx is out of bounds.
+    pub const fn row(&self, idx: usize) -> Result<u64, DisplayArrErr> {
+        match idx {
+            0..=63 => Ok(self.top.as_array()[idx]),
+            64..=127 => Ok(self.bottom.as_array()[idx - 64]),
+            _ => Err(DisplayArrErr::row_err(127, idx)),
+        }
+    }
+
+    /// Sets row `idx` in `self`. Returns [DisplayArrErr::InvalidRowError] if `idx` is out of bounds.
+    pub const fn set_row(&mut self, idx: usize, src: u64) -> Result<(), DisplayArrErr> {
+        match idx {
+            0..=63 => self.top.as_mut_array()[idx] = src,
+            64..=127 => self.bottom.as_mut_array()[idx - 64] = src,
+            _ => return Err(DisplayArrErr::row_err(127, idx)),
+        }
+        Ok(())
+    }
+
+    /// Gets a single boolean bit from `self`, where column 0 is the leftmost. Returns [DisplayArrErr::InvalidRowError]
+    /// or [DisplayArrErr::InvalidColumnError] if either index is out of bounds.
+    pub const fn bit(&self, row_idx: usize, column_idx: usize) -> Result<bool, DisplayArrErr> {
+        let row = match self.row(row_idx) {
+            Ok(row) => row,
+            Err(e) => return Err(e),
+        };
+        if column_idx >= Self::WIDTH {
+            return Err(DisplayArrErr::column_err(Self::WIDTH - 1, column_idx));
+        }
+        Ok(row & (1 << (63 - column_idx)) != 0)
+    }
+
+    /// Rotates `self` 90 degrees clockwise back into a [DisplayArr]. Inverse of [DisplayArr::rotate_90_ccw].
+    pub fn rotate_90_cw(&self) -> DisplayArr {
+        DisplayArr::new_from_halves(
+            transpose_u64x64(self.bottom).reverse_bits(),
+            transpose_u64x64(self.top).reverse_bits(),
+        )
+    }
+    /// Rotates `self` 90 degrees counter-clockwise back into a [DisplayArr]. Inverse of [DisplayArr::rotate_90_cw].
+    pub fn rotate_90_ccw(&self) -> DisplayArr {
+        DisplayArr::new_from_halves(
+            transpose_u64x64(self.top).reverse(),
+            transpose_u64x64(self.bottom).reverse(),
+        )
+    }
 }
 
-impl core::ops::BitAndAssign for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::BitAnd for Framebuffer<W, H, S> {
+    type Output = Self;
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl<const W: usize, const H: usize, const S: usize> core::ops::BitAndAssign
+    for Framebuffer<W, H, S>
+{
     fn bitand_assign(&mut self, rhs: Self) {
-        self.left &= rhs.left;
-        self.right &= rhs.right;
+        for (strip, other) in self.strips.iter_mut().zip(rhs.strips) {
+            *strip &= other;
+        }
     }
 }
 
-impl core::ops::BitOr for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::BitOr for Framebuffer<W, H, S> {
     type Output = Self;
-    fn bitor(self, rhs: Self) -> Self::Output {
-        Self {
-            left: self.left | rhs.left,
-            right: self.right | rhs.right,
-        }
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
     }
 }
 
-impl core::ops::BitOrAssign for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::BitOrAssign
+    for Framebuffer<W, H, S>
+{
     fn bitor_assign(&mut self, rhs: Self) {
-        self.left |= rhs.left;
-        self.right |= rhs.right;
+        for (strip, other) in self.strips.iter_mut().zip(rhs.strips) {
+            *strip |= other;
+        }
     }
 }
 
-impl core::ops::BitXor for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::BitXor for Framebuffer<W, H, S> {
     type Output = Self;
-    fn bitxor(self, rhs: Self) -> Self::Output {
-        Self {
-            left: self.left ^ rhs.left,
-            right: self.right ^ rhs.right,
-        }
+    fn bitxor(mut self, rhs: Self) -> Self::Output {
+        self ^= rhs;
+        self
     }
 }
 
-impl core::ops::BitXorAssign for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::BitXorAssign
+    for Framebuffer<W, H, S>
+{
     fn bitxor_assign(&mut self, rhs: Self) {
-        self.left ^= rhs.left;
-        self.right ^= rhs.right;
+        for (strip, other) in self.strips.iter_mut().zip(rhs.strips) {
+            *strip ^= other;
+        }
     }
 }
 
-impl core::ops::Shl<usize> for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::Shl<usize>
+    for Framebuffer<W, H, S>
+{
     type Output = Self;
 
+    /// Shifts every row left by `rhs` columns, carrying bits from each strip into the strip to its left. Columns
+    /// shifted in are cleared, so shifting by `W` or more clears `self`.
     fn shl(self, rhs: usize) -> Self::Output {
+        if rhs == 0 {
+            return self;
+        }
+        if rhs >= W {
+            return Self::new();
+        }
+        let (skip, bits) = (rhs / 64, rhs % 64);
+        let shift = Simd::splat(bits as u64);
+        let carry = Simd::splat(64 - bits as u64);
         let mut output = Self::new();
-        for line in 0..Self::LEN {
-            let _ = output.set_row(line, unsafe { self.row(line).unwrap_unchecked() } << rhs);
+        for i in 0..Self::STRIPS - skip {
+            let mut strip = self.strips[i + skip] << shift;
+            if bits != 0 && i + skip + 1 < Self::STRIPS {
+                strip |= self.strips[i + skip + 1] >> carry;
+            }
+            output.strips[i] = strip;
         }
         output
     }
 }
 
-impl core::ops::ShlAssign<usize> for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::ShlAssign<usize>
+    for Framebuffer<W, H, S>
+{
     fn shl_assign(&mut self, rhs: usize) {
-        for line in 0..Self::LEN {
-            let _ = self.set_row(line, unsafe { self.row(line).unwrap_unchecked() } << rhs);
-        }
+        *self = *self << rhs;
     }
 }
 
-impl core::ops::Shr<usize> for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::Shr<usize>
+    for Framebuffer<W, H, S>
+{
     type Output = Self;
 
+    /// Shifts every row right by `rhs` columns, carrying bits from each strip into the strip to its right. Columns
+    /// shifted in are cleared, so shifting by `W` or more clears `self`.
     fn shr(self, rhs: usize) -> Self::Output {
+        if rhs == 0 {
+            return self;
+        }
+        if rhs >= W {
+            return Self::new();
+        }
+        let (skip, bits) = (rhs / 64, rhs % 64);
+        let shift = Simd::splat(bits as u64);
+        let carry = Simd::splat(64 - bits as u64);
         let mut output = Self::new();
-        for line in 0..Self::LEN {
-            let _ = output.set_row(line, unsafe { self.row(line).unwrap_unchecked() } >> rhs);
+        for i in skip..Self::STRIPS {
+            let mut strip = self.strips[i - skip] >> shift;
+            if bits != 0 && i > skip {
+                strip |= self.strips[i - skip - 1] << carry;
+            }
+            output.strips[i] = strip & Simd::splat(Self::strip_mask(i));
         }
         output
     }
 }
 
-impl core::ops::ShrAssign<usize> for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::ShrAssign<usize>
+    for Framebuffer<W, H, S>
+{
     fn shr_assign(&mut self, rhs: usize) {
-        for line in 0..Self::LEN {
-            let _ = self.set_row(line, unsafe { self.row(line).unwrap_unchecked() } >> rhs);
-        }
+        *self = *self >> rhs;
     }
 }
 
-impl core::ops::Not for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::Not for Framebuffer<W, H, S> {
     type Output = Self;
-    fn not(self) -> Self::Output {
-        Self {
-            left: !self.left,
-            right: !self.right,
+    fn not(mut self) -> Self::Output {
+        for (i, strip) in self.strips.iter_mut().enumerate() {
+            *strip = !*strip & Simd::splat(Self::strip_mask(i));
         }
+        self
     }
 }
 
-impl core::ops::Neg for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::ops::Neg for Framebuffer<W, H, S> {
     type Output = Self;
     fn neg(self) -> Self::Output {
         !self
     }
 }
 
-impl core::fmt::Display for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::fmt::Display for Framebuffer<W, H, S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        use core::iter::zip;
-
         const MAX_NUM_WIDTH: usize = 2;
 
-        if self.left.lanes() != self.right.lanes() {
-            unreachable!()
-        }
-
-        for row in zip(0.., zip((&self.left).to_array(), (&self.right).to_array())) {
-            let padding_count: usize = MAX_NUM_WIDTH - if row.0 < 10 { 2 } else { 1 };
+        for row in 0..H {
+            let padding_count: usize = MAX_NUM_WIDTH - if row < 10 { 2 } else { 1 };
             let padding = if padding_count == 1 { " " } else { "  " };
-            write!(f, "\n")?;
-            write!(
-                f,
-                "{:}: {}{:0128b}",
-                row.0,
-                padding,
-                Self::combine_u128(row.1 .0, row.1 .1)
-            )?;
-        }
-        write!(f, "\n")?;
+            writeln!(f)?;
+            write!(f, "{:}: {}", row, padding)?;
+            // Rows are always in bounds, and only the columns within the framebuffer are printed.
+            for (i, word) in self.row_words(row).unwrap_or([0; S]).iter().enumerate() {
+                let width = if (i + 1) * 64 <= W { 64 } else { W - i * 64 };
+                write!(f, "{:0width$b}", word >> (64 - width), width = width)?;
+            }
+        }
+        writeln!(f)?;
         Ok(())
     }
 }
 
-impl core::fmt::Debug for DisplayArr {
+impl<const W: usize, const H: usize, const S: usize> core::fmt::Debug for Framebuffer<W, H, S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        use core::iter::zip;
-
         const MAX_NUM_WIDTH: usize = 2;
 
-        if self.left.lanes() != self.right.lanes() {
-            unreachable!()
-        }
-
-        for row in zip(0.., zip((&self.left).to_array(), (&self.right).to_array())) {
-            let padding_count: usize = MAX_NUM_WIDTH - if row.0 < 10 { 2 } else { 1 };
+        for row in 0..H {
+            let padding_count: usize = MAX_NUM_WIDTH - if row < 10 { 2 } else { 1 };
             let padding = if padding_count == 1 { " " } else { "  " };
-            write!(f, "\n")?;
-            write!(
-                f,
-                "{:}: {}{:}",
-                row.0,
-                padding,
-                Self::combine_u128(row.1 .0, row.1 .1)
-            )?;
-        }
-        write!(f, "\n")?;
+            writeln!(f)?;
+            write!(f, "{:}: {}", row, padding)?;
+            let words = self.row_words(row).unwrap_or([0; S]);
+            if W <= 128 {
+                let right = if words.len() > 1 { words[1] } else { 0 };
+                write!(f, "{:}", Self::combine_u128(words[0], right))?;
+            } else {
+                write!(f, "{:?}", words)?;
+            }
+        }
+        writeln!(f)?;
         Ok(())
     }
 }
@@ -445,9 +969,13 @@ impl core::fmt::Debug for DisplayArr {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dirty::FrameDiff;
+    use crate::draw::{draw_rect, DrawMode};
+    use crate::snapshot::assert_snapshot;
+    use crate::text::draw_text;
     use test::Bencher;
     const TEST_U128: u128 = 0x0123456789abcdefu128;
-    const UPPER_U64: u64 = (TEST_U128 << 64) as u64;
+    const UPPER_U64: u64 = (TEST_U128 >> 64) as u64;
     const LOWER_U64: u64 = TEST_U128 as u64;
     const EMPTY_DISPLAYARR: DisplayArr = DisplayArr::new();
     const FULL_DISPLAYARR: DisplayArr = DisplayArr::new_full();
@@ -471,8 +999,8 @@ mod tests {
     #[test]
     fn displayarr_creation() {
         use core::cell;
-        let empty = cell::LazyCell::new(|| DisplayArr::new());
-        let full = cell::LazyCell::new(|| DisplayArr::new_full());
+        let empty = cell::LazyCell::new(DisplayArr::new);
+        let full = cell::LazyCell::new(DisplayArr::new_full);
         assert_eq!(EMPTY_DISPLAYARR, *empty);
         assert_eq!(FULL_DISPLAYARR, *full);
         assert_ne!(FULL_DISPLAYARR, EMPTY_DISPLAYARR);
@@ -505,40 +1033,480 @@ mod tests {
         assert!(EMPTY_DISPLAYARR.column(128).is_err());
     }
 
+    /// Rows where every row differs, and bits are spread over both halves.
+    fn varied_rows() -> [u128; 64] {
+        let mut rows = [0u128; 64];
+        for (i, row) in rows.iter_mut().enumerate() {
+            *row = TEST_U128.rotate_left(i as u32 * 7) ^ (u128::MAX >> i);
+        }
+        rows
+    }
+
+    #[test]
+    fn displayarr_column_bit_order() {
+        // Row 0 is the highest bit of a column, the reverse of the order columns were first returned in.
+        let mut arr = DisplayArr::new();
+        arr.set_bit(0, 5).unwrap();
+        arr.set_bit(63, 70).unwrap();
+        assert_eq!(arr.column(5).unwrap(), 1 << 63);
+        assert_eq!(arr.column(70).unwrap(), 1);
+        assert_eq!(arr.columns()[5], 1 << 63);
+
+        let mut set = DisplayArr::new();
+        set.set_column(5, 1 << 63).unwrap();
+        set.oreq_column(70, 1).unwrap();
+        assert_eq!(set, arr);
+
+        // Rows past the bottom of a short framebuffer are the lowest bits, and are dropped.
+        let mut short = Framebuffer::<128, 32, { strips(128) }>::new();
+        short.set_column(0, u64::MAX).unwrap();
+        assert_eq!(short.column(0).unwrap(), u64::MAX << 32);
+    }
+
+    #[test]
+    fn displayarr_columns() {
+        let varied = DisplayArr::new_from_array(varied_rows());
+        let columns = varied.columns();
+        for (x, &column) in columns.iter().enumerate() {
+            assert_eq!(varied.column(x).unwrap(), column);
+            for y in 0..64 {
+                assert_eq!(
+                    column & (1 << (63 - y)) != 0,
+                    varied.bit(y, x).unwrap(),
+                    "err row {:} column {:}",
+                    y,
+                    x
+                );
+            }
+        }
+        assert_eq!(DisplayArr::new_from_columns(columns), varied);
+        assert_eq!(
+            transpose_u64x64(transpose_u64x64(varied.strips[0])),
+            varied.strips[0]
+        );
+
+        let mut arr = DisplayArr::new();
+        arr.set_column(0, 1 << 63).unwrap();
+        arr.set_column(64, 0b101).unwrap();
+        arr.oreq_column(127, u64::MAX).unwrap();
+        assert_eq!(arr.row(0).unwrap(), (1u128 << 127) | 1);
+        assert_eq!(arr.row(61).unwrap(), (1u128 << 63) | 1);
+        assert_eq!(arr.row(62).unwrap(), 1);
+        assert_eq!(arr.row(63).unwrap(), (1u128 << 63) | 1);
+
+        let mut bars = DisplayArr::new_full();
+        bars.set_column(5, u64::MAX >> 40).unwrap();
+        assert_eq!(bars.column(5).unwrap(), u64::MAX >> 40);
+        assert_eq!(bars.row(39).unwrap(), !(1u128 << 122));
+        assert_eq!(bars.row(40).unwrap(), u128::MAX);
+
+        assert!(matches!(
+            arr.set_column(128, 0),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert!(matches!(
+            arr.oreq_column(128, 0),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+    }
+
+    #[test]
+    fn displayarr_flips() {
+        let rows = varied_rows();
+        let varied = DisplayArr::new_from_array(rows);
+
+        let mut flipped = varied;
+        flipped.flip_horizontal();
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(flipped.row(i).unwrap(), row.reverse_bits());
+        }
+        flipped.flip_horizontal();
+        assert_eq!(flipped, varied);
+
+        flipped.flip_vertical();
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(flipped.row(63 - i).unwrap(), *row);
+        }
+
+        let mut rotated = varied;
+        rotated.rotate_180();
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(rotated.row(63 - i).unwrap(), row.reverse_bits());
+        }
+        rotated.rotate_180();
+        assert_eq!(rotated, varied);
+
+        // Text shows which way round a frame is at a glance.
+        let mut labelled = DisplayArr::new();
+        draw_text(&mut labelled, b"FLIP 123", 2, 2).unwrap();
+        draw_rect(&mut labelled, &Rect::new(0, 0, 60, 12), DrawMode::Set).unwrap();
+        draw_rect(&mut labelled, &Rect::new(100, 40, 20, 20), DrawMode::Set).unwrap();
+        assert_snapshot("displayarr_labelled.txt", &labelled);
+        let mut flipped = labelled;
+        flipped.flip_horizontal();
+        assert_snapshot("displayarr_flip_horizontal.txt", &flipped);
+        let mut flipped = labelled;
+        flipped.flip_vertical();
+        assert_snapshot("displayarr_flip_vertical.txt", &flipped);
+        let mut rotated = labelled;
+        rotated.rotate_180();
+        assert_snapshot("displayarr_rotate_180.txt", &rotated);
+    }
+
+    #[test]
+    fn displayarr_rotate_90() {
+        let varied = DisplayArr::new_from_array(varied_rows());
+        let cw = varied.rotate_90_cw();
+        let ccw = varied.rotate_90_ccw();
+        for y in 0..64 {
+            for x in 0..128 {
+                let pixel = varied.bit(y, x).unwrap();
+                assert_eq!(
+                    cw.bit(x, 63 - y).unwrap(),
+                    pixel,
+                    "err row {:} column {:} cw",
+                    y,
+                    x
+                );
+                assert_eq!(
+                    ccw.bit(127 - x, y).unwrap(),
+                    pixel,
+                    "err row {:} column {:} ccw",
+                    y,
+                    x
+                );
+            }
+        }
+        assert_eq!(cw.rotate_90_ccw(), varied);
+        assert_eq!(ccw.rotate_90_cw(), varied);
+        assert_eq!(cw.rotate_90_cw().rotate_90_cw(), ccw);
+
+        let mut rotated = varied;
+        rotated.rotate_180();
+        assert_eq!(cw.rotate_90_cw(), rotated);
+
+        let mut portrait = PortraitArr::new();
+        portrait.set_row(127, 1 << 63).unwrap();
+        assert_eq!(portrait.row(127).unwrap(), 1 << 63);
+        assert!(portrait.bit(127, 0).unwrap());
+        assert!(matches!(
+            portrait.row(128),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+        assert!(matches!(
+            portrait.bit(0, 64),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        // The bottom-left corner of a portrait frame is the top-left corner once rotated clockwise.
+        assert_eq!(portrait.rotate_90_cw().row(0).unwrap(), 1u128 << 127);
+    }
+
+    #[test]
+    fn framebuffer_sizes() {
+        type Small = Framebuffer<96, 16, { strips(96) }>;
+        type Short = Framebuffer<128, 32, { strips(128) }>;
+        type Wide = Framebuffer<256, 64, { strips(256) }>;
+        assert_eq!(
+            (
+                Small::STRIPS,
+                Short::STRIPS,
+                Wide::STRIPS,
+                DisplayArr::STRIPS
+            ),
+            (2, 2, 4, 2)
+        );
+
+        // Columns past the right edge stay clear, however they are written.
+        let full = Small::new_full();
+        assert_eq!(full.row(15).unwrap(), u128::MAX << 32);
+        assert_eq!(!Small::new(), full);
+        let mut small = Small::new();
+        small.set_row(0, u128::MAX).unwrap();
+        small.oreq_row(1, u128::MAX).unwrap();
+        assert_eq!(small.row(0).unwrap(), u128::MAX << 32);
+        assert_eq!(small.row(1).unwrap(), u128::MAX << 32);
+        assert_eq!(Small::row_mask(90, 20), DisplayArr::row_mask(90, 6));
+        assert_eq!(
+            (full >> 1).row(3).unwrap(),
+            (u128::MAX << 32) >> 1 & (u128::MAX << 32)
+        );
+
+        assert!(matches!(
+            small.row(16),
+            Err(DisplayArrErr::InvalidRowError(DisplayArrIndexError {
+                expected: 15,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            small.set_bit(0, 96),
+            Err(DisplayArrErr::InvalidColumnError(DisplayArrIndexError {
+                expected: 95,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            Small::check_rect(&Rect::new(90, 0, 7, 1)),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+
+        let mut small = Small::new();
+        small.set_bit(2, 0).unwrap();
+        small.set_bit(5, 95).unwrap();
+        small.flip_horizontal();
+        assert!(small.bit(2, 95).unwrap() && small.bit(5, 0).unwrap());
+        small.rotate_180();
+        assert!(small.bit(13, 0).unwrap() && small.bit(10, 95).unwrap());
+        let columns = small.columns();
+        assert_eq!(columns[0], 1 << (63 - 13));
+        assert_eq!(columns[95], 1 << (63 - 10));
+        assert_eq!(Small::new_from_columns(columns), small);
+        assert_eq!(small.column(95).unwrap(), 1 << (63 - 10));
+
+        let mut short = Short::new();
+        short.set_column(127, u64::MAX).unwrap();
+        assert_eq!(short.column(127).unwrap(), u64::MAX << 32);
+        short.rotate_rows_down::<1>();
+        assert_eq!(short.row(0).unwrap(), 1);
+
+        // Shifts carry across all four strips of a wide framebuffer.
+        let mut wide = Wide::new();
+        wide.set_bit(7, 0).unwrap();
+        for x in 0..256 {
+            let shifted = wide >> x;
+            assert!(shifted.bit(7, x).unwrap(), "err shift {:}", x);
+            assert_eq!(shifted << x, wide, "err shift {:}", x);
+        }
+        assert_eq!(wide >> 256, Wide::new());
+        assert_eq!(wide.row_words(7).unwrap(), [1 << 63, 0, 0, 0]);
+        wide.set_row_words(8, [0, 0, 0, 1]).unwrap();
+        assert!(wide.bit(8, 255).unwrap());
+        wide.flip_horizontal();
+        assert!(wide.bit(7, 255).unwrap() && wide.bit(8, 0).unwrap());
+        assert_eq!(
+            format!("{}", Wide::new_full())
+                .lines()
+                .nth(1)
+                .unwrap()
+                .len(),
+            256 + 5
+        );
+    }
+
+    #[test]
+    fn displayarr_bit_order() {
+        // Column 0 of each half is the highest bit of that half's row word, so `bit(r, 0)` is the highest bit of
+        // `row(r)`. Before columns were numbered from the left, `bit(r, 0)` read the lowest bit of the left half.
+        for r in [0, 31, 63] {
+            let mut arr = DisplayArr::new();
+            arr.set_row(r, 1u128 << 127).unwrap();
+            assert!(arr.bit(r, 0).unwrap());
+            assert!(!arr.bit(r, 63).unwrap());
+
+            arr.set_row(r, 1u128 << 63).unwrap();
+            assert!(arr.bit(r, 64).unwrap());
+            assert!(!arr.bit(r, 127).unwrap() && !arr.bit(r, 63).unwrap());
+
+            arr.set_row(r, 1).unwrap();
+            assert!(arr.bit(r, 127).unwrap() && !arr.bit(r, 64).unwrap());
+        }
+        let halves = DisplayArr::splat(1 << 63, 1 << 63);
+        assert!(halves.bit(10, 0).unwrap() && halves.bit(10, 64).unwrap());
+        assert!(!halves.bit(10, 63).unwrap() && !halves.bit(10, 127).unwrap());
+    }
+
+    #[test]
+    fn displayarr_pixel_ops() {
+        let mut arr = DisplayArr::new();
+        arr.set_bit(3, 0).unwrap();
+        arr.set_bit(3, 63).unwrap();
+        arr.set_bit(3, 64).unwrap();
+        arr.set_bit(3, 127).unwrap();
+        assert_eq!(arr.row(3).unwrap(), (1u128 << 127) | (0b11u128 << 63) | 1);
+        for column in [0, 63, 64, 127] {
+            assert!(arr.bit(3, column).unwrap());
+        }
+        assert!(!arr.bit(3, 1).unwrap());
+        assert!(!arr.bit(2, 0).unwrap());
+
+        arr.clear_bit(3, 63).unwrap();
+        arr.clear_bit(4, 63).unwrap();
+        arr.toggle_bit(3, 64).unwrap();
+        arr.toggle_bit(5, 100).unwrap();
+        assert_eq!(arr.row(3).unwrap(), (0b1u128 << 127) | 1);
+        assert_eq!(arr.row(4).unwrap(), 0);
+        assert_eq!(arr.row(5).unwrap(), 1u128 << 27);
+
+        let mut cleared = FULL_DISPLAYARR;
+        cleared.clear_bit(63, 127).unwrap();
+        assert!(!cleared.bit(63, 127).unwrap());
+        assert_eq!(cleared.row(63).unwrap(), u128::MAX - 1);
+
+        assert!(matches!(
+            arr.set_bit(64, 0),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+        assert!(matches!(
+            arr.clear_bit(0, 128),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert!(matches!(
+            arr.toggle_bit(64, 128),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+        assert!(matches!(
+            arr.bit(64, 128),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+        assert!(matches!(
+            arr.bit(0, 128),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+
+        let mut batched = DisplayArr::new();
+        batched.set_pixels((0..64).map(|i| (i, i * 2))).unwrap();
+        for i in 0..64 {
+            assert_eq!(batched.row(i).unwrap(), 1u128 << (127 - i * 2));
+        }
+        assert_snapshot("displayarr_diagonal.pbm", &batched);
+
+        let before = batched;
+        assert!(matches!(
+            batched.set_pixels([(10, 10), (0, 200)]),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert_eq!(batched, before);
+    }
+
     #[test]
     fn displayarr_row_ops() {
         const EXPECTED_VAL: u128 = 128 << 64;
         let test = DisplayArr::splat(128u64, 0u64);
         for x in 0..128 {
             for i in 0..64 {
-                assert_eq!(test.row(i).unwrap() << x, EXPECTED_VAL << x, "err row {:} shift {:} op {}", i, x, "<<");
+                assert_eq!(
+                    test.row(i).unwrap() << x,
+                    EXPECTED_VAL << x,
+                    "err row {:} shift {:} op {}",
+                    i,
+                    x,
+                    "<<"
+                );
             }
         }
 
         for x in 0..128 {
             for i in 0..64 {
-                assert_eq!(test.row(i).unwrap() >> x, EXPECTED_VAL >> x, "err row {:} shift {:} op {}", i, x, ">>");
+                assert_eq!(
+                    test.row(i).unwrap() >> x,
+                    EXPECTED_VAL >> x,
+                    "err row {:} shift {:} op {}",
+                    i,
+                    x,
+                    ">>"
+                );
             }
         }
 
-        // for x in 0..128 {
-        //     for i in 0..64 {
-        //         assert_eq!((test << x).row(i).unwrap(), EXPECTED_VAL << x, "err row {:} shift {:} op {}", i, x, "<< arr");
-        //     }
-        // }
+        for x in 0..=128 {
+            let expected = EXPECTED_VAL.checked_shl(x as u32).unwrap_or(0);
+            for i in 0..64 {
+                assert_eq!(
+                    (test << x).row(i).unwrap(),
+                    expected,
+                    "err row {:} shift {:} op {}",
+                    i,
+                    x,
+                    "<< arr"
+                );
+            }
+        }
 
-        // for x in 0..128 {
-        //     for i in 0..64 {
-        //         assert_eq!((test >> x).row(i).unwrap(), EXPECTED_VAL >> x, "err row {:} shift {:} op {}", i, x, ">> arr");
-        //     }
-        // }
+        for x in 0..=128 {
+            let expected = EXPECTED_VAL.checked_shr(x as u32).unwrap_or(0);
+            for i in 0..64 {
+                assert_eq!(
+                    (test >> x).row(i).unwrap(),
+                    expected,
+                    "err row {:} shift {:} op {}",
+                    i,
+                    x,
+                    ">> arr"
+                );
+            }
+        }
+
+        // Bits cross between the halves in both directions.
+        let rows = varied_rows();
+        let varied = DisplayArr::new_from_array(rows);
+        for x in 0..=128 {
+            let mut shl_assigned = varied;
+            shl_assigned <<= x;
+            let mut shr_assigned = varied;
+            shr_assigned >>= x;
+            for (i, row) in rows.iter().enumerate() {
+                let left = row.checked_shl(x as u32).unwrap_or(0);
+                let right = row.checked_shr(x as u32).unwrap_or(0);
+                assert_eq!(
+                    (varied << x).row(i).unwrap(),
+                    left,
+                    "err row {:} shift {:} op {}",
+                    i,
+                    x,
+                    "<< varied"
+                );
+                assert_eq!(
+                    (varied >> x).row(i).unwrap(),
+                    right,
+                    "err row {:} shift {:} op {}",
+                    i,
+                    x,
+                    ">> varied"
+                );
+                assert_eq!(shl_assigned.row(i).unwrap(), left);
+                assert_eq!(shr_assigned.row(i).unwrap(), right);
+            }
+        }
+        assert_eq!(varied << 200, DisplayArr::new());
+        assert_eq!(varied >> 200, DisplayArr::new());
+
+        // Text straddling the halves shows whole columns crossing between them, and falling off the right edge.
+        let mut label = DisplayArr::new();
+        draw_text(&mut label, b"SHIFT", 54, 1).unwrap();
+        let mut right = label >> 60;
+        right.rotate_rows_down::<8>();
+        let mut left = label << 40;
+        left.rotate_rows_down::<16>();
+        assert_snapshot("displayarr_row_ops.txt", &(label | right | left));
 
         assert_eq!(test ^ test, DisplayArr::new());
-        assert_ne!(test  ^ test, test);
+        assert_ne!(test ^ test, test);
     }
 
     #[bench]
     fn bench_cmp_all(b: &mut Bencher) {
-        for _ in 0..3 {b.iter(|| FULL_DISPLAYARR == EMPTY_DISPLAYARR);}
+        for _ in 0..3 {
+            b.iter(|| FULL_DISPLAYARR == EMPTY_DISPLAYARR);
+        }
+    }
+
+    #[bench]
+    fn bench_diff(b: &mut Bencher) {
+        let previous = DisplayArr::new_from_array(varied_rows());
+        let mut current = previous;
+        current.toggle_bit(40, 70).unwrap();
+        b.iter(|| FrameDiff::new(&test::black_box(previous), &test::black_box(current)).bounds());
+    }
+
+    #[bench]
+    fn bench_columns(b: &mut Bencher) {
+        let varied = DisplayArr::new_from_array(varied_rows());
+        b.iter(|| test::black_box(varied).columns());
+    }
+
+    #[bench]
+    fn bench_shift(b: &mut Bencher) {
+        let test = DisplayArr::splat(0x0123456789abcdef, 0xfedcba9876543210);
+        b.iter(|| (test::black_box(test) << test::black_box(37)) >> test::black_box(90));
     }
 }
@@ -9,10 +9,6 @@
 // #![feature(rustc_attrs)]
 // #![feature(portable_simd)]
 
-// type StandardCharacter = u16; // 5 x 3, with sign bit as visibility
-// type WideCharacter = u32; // 5 x 5, with sign bit as visibility and 6 trailing unused 0s
-// type SmallCharacter = u8; // 4x2
-
 // type DisplayLine = u128;
 // type DisplayPixels = [DisplayLine; 64];
 
@@ -21,194 +17,6 @@
 //     fn into_displaypixels(self) -> DisplayPixels;
 // }
 
-// const STDCHAR_LENGTH: usize = 5;
-// const STDCHAR_WIDTH: usize = 3;
-// const WIDECHAR_LENGTH: usize = 5;
-// const WIDECHAR_WIDTH: usize = 5;
-// const SMALLCHAR_LENGTH: usize = 3;
-// const SMALLCHAR_WIDTH: usize = 2;
-
-// const SPACE_PIXELS: StandardCharacter = 0b1000000000000000u16;
-// const EXCL_PIXELS: StandardCharacter = 0b1010010010000010u16;
-// const QUOTE_PIXELS: StandardCharacter = 0b1101101000000000u16;
-// const HASH_PIXELS: WideCharacter = 0b10101011111010101111101010000000u32;
-// const DOLLAR_PIXELS: WideCharacter = 0b10111010100011100010101110000000u32;
-
-// const AT_PIXELS: WideCharacter = 0b11111110001101111011011111000000u32;
-// const PERCENT_PIXELS: StandardCharacter = 0b1101001010100101u16;
-// const CARET_PIXELS: StandardCharacter = 0b1010101101000000u16;
-// const AMPERSAND_PIXELS: WideCharacter = 0b10110010000011011001001101000000u32;
-// const ASTERISK_PIXELS: StandardCharacter = 0b1101010101000000u16;
-// const LEFT_PAREN_PIXELS: StandardCharacter = 0b1001010010010001u16;
-// const RIGHT_PAREN_PIXELS: StandardCharacter = 0b1100010010010100u16;
-// const MINUS_PIXELS: StandardCharacter = 0b1000000111000000u16;
-// // TODO: finish ASCII
-// const A_PIXELS: StandardCharacter = 0b1111101111101101u16;
-// const B_PIXELS: StandardCharacter = 0b1110101110101110u16;
-// const C_PIXELS: StandardCharacter = 0b1111100100100111u16;
-// const D_PIXELS: StandardCharacter = 0b1110101101101110u16;
-// const E_PIXELS: StandardCharacter = 0b1111100111100111u16;
-// const F_PIXELS: StandardCharacter = 0b1111100111100100u16;
-// const G_PIXELS: StandardCharacter = 0b1111100101101111u16;
-// const H_PIXELS: StandardCharacter = 0b1101101111101101u16;
-// const I_PIXELS: StandardCharacter = 0b1111010010010111u16;
-// const J_PIXELS: StandardCharacter = 0b1111010010010110u16;
-// const K_PIXELS: StandardCharacter = 0b1101101110101101u16;
-// const L_PIXELS: StandardCharacter = 0b1100100100100111u16;
-// const M_PIXELS: WideCharacter = 0b11101110101101011000110001000000u32;
-// const N_PIXELS: StandardCharacter = 0b1110101101101101u16;
-// const O_PIXELS: StandardCharacter = 0b1111101101101111u16;
-// const P_PIXELS: StandardCharacter = 0b1111101111100100u16;
-// const Q_PIXELS: StandardCharacter = 0b1111101101111001u16;
-// const R_PIXELS: StandardCharacter = 0b1111101110101101u16;
-// const S_PIXELS: StandardCharacter = 0b1111100111001111u16;
-// const T_PIXELS: StandardCharacter = 0b1111010010010010u16;
-// const U_PIXELS: StandardCharacter = 0b1101101101101111u16;
-// const V_PIXELS: StandardCharacter = 0b1101101101101010u16;
-// const W_PIXELS: WideCharacter = 0b11000110001101011010111011000000u32;
-// const X_PIXELS: StandardCharacter = 0b1101101010101101u16;
-// const Y_PIXELS: StandardCharacter = 0b1101101101010010u16;
-// const Z_PIXELS: StandardCharacter = 0b1111001010100111u16;
-// const ZERO_PIXELS: StandardCharacter = 0b1111101101101111u16;
-// const ONE_PIXELS: StandardCharacter = 0b1110010010010111u16;
-// const TWO_PIXELS: StandardCharacter = 0b1111001011100111u16;
-// const THREE_PIXELS: StandardCharacter = 0b1111001111001111u16;
-// const FOUR_PIXELS: StandardCharacter = 0b1101101111001001u16;
-// const FIVE_PIXELS: StandardCharacter = 0b1111100111001110u16;
-// const SIX_PIXELS: StandardCharacter = 0b1111100111101111u16;
-// const SEVEN_PIXELS: StandardCharacter = 0b1111001010010010u16;
-// const EIGHT_PIXELS: StandardCharacter = 0b1111101111101111u16;
-// const NINE_PIXELS: StandardCharacter = 0b1111101111001001u16;
-// const SLASH_PIXELS: StandardCharacter = 0b0001001010100100u16;
-// const DOT_PIXELS: SmallCharacter = 0b01100110u8;
-// const COMMA_PIXELS: SmallCharacter = 0b01100010u8;
-// const UNKNOWN_PIXELS: StandardCharacter = 0b1101010101010101u16;
-
-// pub enum DisplayCharacter {
-//     Standard(StandardCharacter),
-//     Wide(WideCharacter),
-//     Small(SmallCharacter),
-// }
-
-// impl DisplayCharacter {
-//     pub const fn new_from_ascii(character: u8) -> Self {
-//         match character.to_ascii_uppercase() {
-//             b'A' => return Self::Standard(A_PIXELS),
-//             b'B' => return Self::Standard(B_PIXELS),
-//             b'C' => return Self::Standard(C_PIXELS),
-//             b'D' => return Self::Standard(D_PIXELS),
-//             b'E' => return Self::Standard(E_PIXELS),
-//             b'F' => return Self::Standard(F_PIXELS),
-//             b'G' => return Self::Standard(G_PIXELS),
-//             b'H' => return Self::Standard(H_PIXELS),
-//             b'I' => return Self::Standard(I_PIXELS),
-//             b'J' => return Self::Standard(J_PIXELS),
-//             b'K' => return Self::Standard(K_PIXELS),
-//             b'L' => return Self::Standard(L_PIXELS),
-//             b'M' => return Self::Wide(M_PIXELS),
-//             b'N' => return Self::Standard(N_PIXELS),
-//             b'O' => return Self::Standard(O_PIXELS),
-//             b'P' => return Self::Standard(P_PIXELS),
-//             b'Q' => return Self::Standard(Q_PIXELS),
-//             b'R' => return Self::Standard(R_PIXELS),
-//             b'S' => return Self::Standard(S_PIXELS),
-//             b'T' => return Self::Standard(T_PIXELS),
-//             b'U' => return Self::Standard(U_PIXELS),
-//             b'V' => return Self::Standard(V_PIXELS),
-//             b'W' => return Self::Wide(W_PIXELS),
-//             b'X' => return Self::Standard(X_PIXELS),
-//             b'Y' => return Self::Standard(Y_PIXELS),
-//             b'Z' => return Self::Standard(Z_PIXELS),
-//             b'0' => return Self::Standard(ZERO_PIXELS),
-//             b'1' => return Self::Standard(ONE_PIXELS),
-//             b'2' => return Self::Standard(TWO_PIXELS),
-//             b'3' => return Self::Standard(THREE_PIXELS),
-//             b'4' => return Self::Standard(FOUR_PIXELS),
-//             b'5' => return Self::Standard(FIVE_PIXELS),
-//             b'6' => return Self::Standard(SIX_PIXELS),
-//             b'7' => return Self::Standard(SEVEN_PIXELS),
-//             b'8' => return Self::Standard(EIGHT_PIXELS),
-//             b'9' => return Self::Standard(NINE_PIXELS),
-//             b'/' => return Self::Standard(SLASH_PIXELS),
-//             b'.' => return Self::Small(DOT_PIXELS),
-//             b',' => return Self::Small(COMMA_PIXELS),
-//             b' ' => return Self::Standard(SPACE_PIXELS),
-//             b'!' => return Self::Standard(EXCL_PIXELS),
-//             b'@' => return Self::Wide(AT_PIXELS),
-//             b'#' => return Self::Wide(HASH_PIXELS),
-//             b'%' => return Self::Standard(PERCENT_PIXELS),
-//             b'^' => return Self::Standard(CARET_PIXELS),
-//             b'&' => return Self::Wide(AMPERSAND_PIXELS),
-//             b'*' => return Self::Standard(ASTERISK_PIXELS),
-//             b')' => return Self::Standard(LEFT_PAREN_PIXELS),
-//             b'(' => return Self::Standard(RIGHT_PAREN_PIXELS),
-//             b'-' => return Self::Standard(MINUS_PIXELS),
-//             _ => return Self::Standard(UNKNOWN_PIXELS),
-//         };
-//     }
-// }
-
-// impl DisplayCharacter {
-//     const fn into_displaypixels(self) -> DisplayPixels {
-//         let mut output: DisplayPixels = [0u128; 64];
-//         let mut i: usize = 0;
-
-//         macro_rules! handle_pixel_arm {
-//             ($type: ty, $pixel_mask: literal, $length: expr, $width: expr, $i: expr, $data: expr, $output: expr, $offset: literal) => {
-//                 while $i < $length {
-//                     let x: u128 = (($data & ($pixel_mask >> ($i * $width)))
-//                         >> ((($length - ($i + 1)) * $width) + $offset) as $type)
-//                         as u128;
-//                     $output[$i] |= x;
-//                     $i += 1;
-//                 }
-//             };
-//         }
-
-//         match self {
-//             Self::Standard(data) => {
-//                 handle_pixel_arm!(
-//                     u16,
-//                     0x7000u16,
-//                     STDCHAR_LENGTH,
-//                     STDCHAR_WIDTH,
-//                     i,
-//                     data,
-//                     output,
-//                     0
-//                 );
-//             }
-
-//             Self::Wide(data) => {
-//                 handle_pixel_arm!(
-//                     u32,
-//                     0x7C000000u32,
-//                     WIDECHAR_LENGTH,
-//                     WIDECHAR_WIDTH,
-//                     i,
-//                     data,
-//                     output,
-//                     6
-//                 );
-//             }
-
-//             Self::Small(data) => {
-//                 handle_pixel_arm!(
-//                     u8,
-//                     0xFu8,
-//                     SMALLCHAR_LENGTH,
-//                     SMALLCHAR_WIDTH,
-//                     i,
-//                     data,
-//                     output,
-//                     0
-//                 );
-//             }
-//         };
-//         output
-//     }
-// }
-
 // const fn andeq_displaypixels(dest: &mut DisplayPixels, src: &DisplayPixels) {
 //     let mut i: usize = 0;
 //     while i < dest.len() {
@@ -299,38 +107,6 @@
 //     output
 // };
 
-// const fn pixelate_text(text: &[u8]) -> DisplayPixels {
-//     let mut output: DisplayPixels = [0u128; 64];
-//     let mut i: usize = text.len();
-
-//     let mut right_cursor: usize = 127;
-
-//     while i > 0 {
-//         i -= 1;
-//         let letter: u8 = text[i];
-//         let this_letter = DisplayCharacter::new_from_ascii(letter);
-//         let (letter_length, letter_width) = match this_letter {
-//             DisplayCharacter::Small(_) => (SMALLCHAR_LENGTH, SMALLCHAR_WIDTH),
-//             DisplayCharacter::Standard(_) => (STDCHAR_LENGTH, STDCHAR_WIDTH),
-//             DisplayCharacter::Wide(_) => (WIDECHAR_LENGTH, WIDECHAR_WIDTH),
-//         };
-
-//         let left_shift: usize = 127 - right_cursor;
-//         let mut x: usize = 0;
-
-//         let letter_pixels = this_letter.into_displaypixels();
-
-//         while x < letter_length {
-//             output[x] |= letter_pixels[x] << left_shift;
-//             x += 1;
-//         }
-
-//         right_cursor = usize::checked_sub(right_cursor, letter_width + 1).unwrap();
-//     }
-
-//     output
-// }
-
 // #[rustc_do_not_const_check]
 // /// Moves text vertically
 // pub const fn rotate_array<T, const S: usize>(array: [T; S], k: isize) -> [T; S] {
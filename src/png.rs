@@ -0,0 +1,399 @@
+use crate::newspeed::Framebuffer;
+
+/// The eight bytes every PNG starts with.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// Bytes in the data of an IHDR chunk.
+const IHDR_LEN: usize = 13;
+/// Bytes of a chunk besides its data: the length, type and CRC.
+const CHUNK_OVERHEAD: usize = 12;
+/// Most bytes a stored deflate block can hold.
+const MAX_STORED_BLOCK: usize = u16::MAX as usize;
+/// zlib header for a deflate stream with a 32 KiB window and no preset dictionary, which is a multiple of 31 as zlib
+/// requires.
+const ZLIB_HEADER: [u8; 2] = [0x78, 0x01];
+
+/// Reversed CRC-32 polynomial used by PNG.
+const CRC_POLYNOMIAL: u32 = 0xedb8_8320;
+
+/// CRC of each byte value, for [Crc32].
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i: usize = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut k: usize = 0;
+        while k < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            k += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// The CRC-32 used by PNG chunks, gzip and zip, computed a byte at a time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    pub const fn new() -> Self {
+        Self(u32::MAX)
+    }
+
+    pub const fn update(&mut self, byte: u8) {
+        self.0 = CRC_TABLE[((self.0 ^ byte as u32) & 0xff) as usize] ^ (self.0 >> 8);
+    }
+
+    pub const fn finish(&self) -> u32 {
+        !self.0
+    }
+
+    /// Gets the CRC of the whole of `data`.
+    pub const fn of(data: &[u8]) -> u32 {
+        let mut crc = Self::new();
+        let mut i: usize = 0;
+        while i < data.len() {
+            crc.update(data[i]);
+            i += 1;
+        }
+        crc.finish()
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The Adler-32 checksum which ends a zlib stream, computed a byte at a time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    /// Largest prime below 2^16.
+    const MODULUS: u32 = 65521;
+
+    pub const fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+
+    pub const fn update(&mut self, byte: u8) {
+        self.a = (self.a + byte as u32) % Self::MODULUS;
+        self.b = (self.b + self.a) % Self::MODULUS;
+    }
+
+    pub const fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    /// Gets the checksum of the whole of `data`.
+    pub const fn of(data: &[u8]) -> u32 {
+        let mut adler = Self::new();
+        let mut i: usize = 0;
+        while i < data.len() {
+            adler.update(data[i]);
+            i += 1;
+        }
+        adler.finish()
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gets the length of the uncompressed image data of a PNG of a `width` x `height` frame scaled by `scale`: each row is
+/// a filter type byte followed by 8 pixels per byte, padded to a whole byte.
+const fn raw_len(width: usize, height: usize, scale: usize) -> usize {
+    height * scale * (1 + (width * scale).div_ceil(8))
+}
+
+/// Gets the length of the IDAT chunk's data: the zlib header, a header for each stored block, the image data and the
+/// Adler-32 checksum.
+const fn idat_len(width: usize, height: usize, scale: usize) -> usize {
+    let raw = raw_len(width, height, scale);
+    ZLIB_HEADER.len() + 5 * raw.div_ceil(MAX_STORED_BLOCK) + raw + 4
+}
+
+/// Gets the length of the PNG [write_png] makes of a `width` x `height` frame with `scale`, such as for sizing a
+/// buffer.
+pub const fn png_len(width: usize, height: usize, scale: usize) -> usize {
+    SIGNATURE.len() + 3 * CHUNK_OVERHEAD + IHDR_LEN + idat_len(width, height, scale)
+}
+
+/// Writes a chunk's bytes to a sink, keeping the CRC of everything after its length.
+struct ChunkWriter<'s> {
+    sink: &'s mut dyn FnMut(u8),
+    crc: Crc32,
+}
+
+impl<'s> ChunkWriter<'s> {
+    /// Writes the length and type of a chunk, then returns a writer for its data.
+    fn begin(sink: &'s mut dyn FnMut(u8), kind: &[u8; 4], length: usize) -> Self {
+        (length as u32)
+            .to_be_bytes()
+            .iter()
+            .for_each(|&byte| sink(byte));
+        let mut writer = Self {
+            sink,
+            crc: Crc32::new(),
+        };
+        writer.write(kind);
+        writer
+    }
+
+    fn byte(&mut self, byte: u8) {
+        self.crc.update(byte);
+        (self.sink)(byte);
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        data.iter().for_each(|&byte| self.byte(byte));
+    }
+
+    /// Writes the CRC which ends the chunk.
+    fn end(self) {
+        let crc = self.crc.finish();
+        crc.to_be_bytes().iter().for_each(|&byte| (self.sink)(byte));
+    }
+}
+
+/// Gets byte `idx` of a row from [Framebuffer::row_words] `width` columns wide, stretched `scale` times wider, where
+/// column 0 is the highest bit of byte 0. Bits past the end of the row are clear.
+fn scaled_byte(row: &[u64], width: usize, idx: usize, scale: usize) -> u8 {
+    let mut byte: u8 = 0;
+    for bit in 0..8 {
+        let x = (idx * 8 + bit) / scale;
+        if x < width && row[x / 64] & (1 << (63 - x % 64)) != 0 {
+            byte |= 0x80 >> bit;
+        }
+    }
+    byte
+}
+
+/// Writes `display` to `sink` as a 1 bit greyscale PNG, where lit pixels are white like on the panel. Each pixel
+/// becomes a `scale` x `scale` square, which makes screenshots readable at desktop sizes. The image data is stored
+/// without compression, so this needs no allocation or tables beyond the CRC's, and writes exactly
+/// [png_len]`(W, H, scale)` bytes. Panics if `scale` is 0.
+pub fn write_png<const W: usize, const H: usize, const S: usize>(
+    display: &Framebuffer<W, H, S>,
+    scale: usize,
+    sink: &mut dyn FnMut(u8),
+) {
+    if scale == 0 {
+        panic!("PNGs cannot be scaled by 0");
+    }
+    let (width, height) = (W * scale, H * scale);
+    SIGNATURE.iter().for_each(|&byte| sink(byte));
+
+    let mut ihdr = ChunkWriter::begin(sink, b"IHDR", IHDR_LEN);
+    ihdr.write(&(width as u32).to_be_bytes());
+    ihdr.write(&(height as u32).to_be_bytes());
+    // Bit depth 1, greyscale, deflate, the standard filter method and no interlacing.
+    ihdr.write(&[1, 0, 0, 0, 0]);
+    ihdr.end();
+
+    let mut idat = ChunkWriter::begin(sink, b"IDAT", idat_len(W, H, scale));
+    idat.write(&ZLIB_HEADER);
+    let raw = raw_len(W, H, scale);
+    let mut adler = Adler32::new();
+    let mut written: usize = 0;
+    let mut emit = |idat: &mut ChunkWriter, byte: u8| {
+        if written.is_multiple_of(MAX_STORED_BLOCK) {
+            let length = (raw - written).min(MAX_STORED_BLOCK) as u16;
+            let last = written + MAX_STORED_BLOCK >= raw;
+            idat.byte(last as u8);
+            idat.write(&length.to_le_bytes());
+            idat.write(&(!length).to_le_bytes());
+        }
+        adler.update(byte);
+        idat.byte(byte);
+        written += 1;
+    };
+    for y in 0..height {
+        // Rows below H are always in bounds.
+        let row = display.row_words(y / scale).unwrap_or([0; S]);
+        emit(&mut idat, 0);
+        for idx in 0..width.div_ceil(8) {
+            emit(&mut idat, scaled_byte(&row, W, idx, scale));
+        }
+    }
+    idat.write(&adler.finish().to_be_bytes());
+    idat.end();
+
+    ChunkWriter::begin(sink, b"IEND", 0).end();
+}
+
+/// Encodes `display` like [write_png] into the start of `out`, returning the number of bytes written. Panics if `out`
+/// is shorter than [png_len]`(W, H, scale)`.
+pub fn encode_png<const W: usize, const H: usize, const S: usize>(
+    display: &Framebuffer<W, H, S>,
+    scale: usize,
+    out: &mut [u8],
+) -> usize {
+    let mut written: usize = 0;
+    write_png(display, scale, &mut |byte| {
+        out[written] = byte;
+        written += 1;
+    });
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::{draw_circle, DrawMode};
+    use crate::newspeed::{strips, DisplayArr};
+    use crate::text::draw_text;
+
+    fn screen() -> DisplayArr {
+        let mut display = DisplayArr::new();
+        draw_text(&mut display, b"PNG 88", 2, 2).unwrap();
+        draw_circle(&mut display, 96, 32, 24, DrawMode::Set);
+        display.set_bit(63, 127).unwrap();
+        display
+    }
+
+    /// Reads a PNG made by [write_png] back into its width, height and pixels, checking every checksum on the way.
+    fn decode(png: &[u8]) -> (usize, usize, Vec<Vec<bool>>) {
+        assert_eq!(png[..8], SIGNATURE);
+        let mut chunks = Vec::new();
+        let mut i = 8;
+        while i < png.len() {
+            let length = u32::from_be_bytes(png[i..i + 4].try_into().unwrap()) as usize;
+            let body = &png[i + 4..i + 8 + length];
+            let crc = u32::from_be_bytes(png[i + 8 + length..i + 12 + length].try_into().unwrap());
+            assert_eq!(Crc32::of(body), crc);
+            chunks.push((&body[..4], &body[4..]));
+            i += CHUNK_OVERHEAD + length;
+        }
+        assert_eq!(i, png.len());
+        let kinds: Vec<&[u8]> = chunks.iter().map(|chunk| chunk.0).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+
+        let ihdr = chunks[0].1;
+        let width = u32::from_be_bytes(ihdr[..4].try_into().unwrap()) as usize;
+        let height = u32::from_be_bytes(ihdr[4..8].try_into().unwrap()) as usize;
+        assert_eq!(ihdr[8..], [1, 0, 0, 0, 0]);
+
+        let zlib = chunks[1].1;
+        assert_eq!(zlib[..2], ZLIB_HEADER);
+        assert_eq!(u16::from_be_bytes([zlib[0], zlib[1]]) % 31, 0);
+        let mut raw = Vec::new();
+        let mut i = 2;
+        loop {
+            let last = zlib[i] & 1 != 0;
+            assert_eq!(zlib[i] >> 1, 0, "blocks are stored");
+            let length = u16::from_le_bytes([zlib[i + 1], zlib[i + 2]]);
+            assert_eq!(!length, u16::from_le_bytes([zlib[i + 3], zlib[i + 4]]));
+            raw.extend_from_slice(&zlib[i + 5..i + 5 + length as usize]);
+            i += 5 + length as usize;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(
+            u32::from_be_bytes(zlib[i..].try_into().unwrap()),
+            Adler32::of(&raw)
+        );
+
+        let stride = 1 + width.div_ceil(8);
+        assert_eq!(raw.len(), height * stride);
+        let pixels = raw
+            .chunks(stride)
+            .map(|line| {
+                assert_eq!(line[0], 0);
+                (0..width)
+                    .map(|x| line[1 + x / 8] & (0x80 >> (x % 8)) != 0)
+                    .collect()
+            })
+            .collect();
+        (width, height, pixels)
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(Crc32::of(b"123456789"), 0xcbf4_3926);
+        assert_eq!(Crc32::of(b"IEND"), 0xae42_6082);
+        assert_eq!(Adler32::of(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(Adler32::of(&[]), 1);
+    }
+
+    #[test]
+    fn headers() {
+        let mut png = [0u8; png_len(128, 64, 1)];
+        assert_eq!(
+            encode_png(&DisplayArr::new(), 1, &mut png),
+            png_len(128, 64, 1)
+        );
+        assert_eq!(png[8..16], [0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+        assert_eq!(png[16..29], [0, 0, 0, 128, 0, 0, 0, 64, 1, 0, 0, 0, 0]);
+        assert_eq!(
+            png[png.len() - 12..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn pixels() {
+        let display = screen();
+        // A scale of 8 needs more than one stored block.
+        for scale in [1, 3, 8] {
+            let mut png = Vec::new();
+            write_png(&display, scale, &mut |byte| png.push(byte));
+            assert_eq!(png.len(), png_len(128, 64, scale));
+            let (width, height, pixels) = decode(&png);
+            assert_eq!((width, height), (128 * scale, 64 * scale));
+            for (y, line) in pixels.iter().enumerate() {
+                for (x, &pixel) in line.iter().enumerate() {
+                    assert_eq!(pixel, display.bit(y / scale, x / scale).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn other_sizes() {
+        // 20 columns pad each row to a whole byte, and 200 columns need more than a u128 row.
+        let mut narrow = Framebuffer::<20, 3, { strips(20) }>::new();
+        narrow.set_bit(0, 0).unwrap();
+        narrow.set_bit(2, 19).unwrap();
+        let mut wide = Framebuffer::<200, 2, { strips(200) }>::new();
+        wide.set_bit(1, 150).unwrap();
+        wide.set_bit(0, 199).unwrap();
+        for scale in [1, 3] {
+            let mut png = Vec::new();
+            write_png(&narrow, scale, &mut |byte| png.push(byte));
+            assert_eq!(png.len(), png_len(20, 3, scale));
+            let (width, height, pixels) = decode(&png);
+            assert_eq!((width, height), (20 * scale, 3 * scale));
+            for (y, line) in pixels.iter().enumerate() {
+                for (x, &pixel) in line.iter().enumerate() {
+                    assert_eq!(pixel, narrow.bit(y / scale, x / scale).unwrap());
+                }
+            }
+
+            let mut png = Vec::new();
+            write_png(&wide, scale, &mut |byte| png.push(byte));
+            assert_eq!(png.len(), png_len(200, 2, scale));
+            let (width, height, pixels) = decode(&png);
+            assert_eq!((width, height), (200 * scale, 2 * scale));
+            for (y, line) in pixels.iter().enumerate() {
+                for (x, &pixel) in line.iter().enumerate() {
+                    assert_eq!(pixel, wide.bit(y / scale, x / scale).unwrap());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+
+use crate::newspeed::{DisplayArr, DisplayArrErr};
+
+/// Segment bits, from the top segment clockwise, then the middle segment.
+pub const SEG_A: u8 = 1 << 0;
+pub const SEG_B: u8 = 1 << 1;
+pub const SEG_C: u8 = 1 << 2;
+pub const SEG_D: u8 = 1 << 3;
+pub const SEG_E: u8 = 1 << 4;
+pub const SEG_F: u8 = 1 << 5;
+pub const SEG_G: u8 = 1 << 6;
+
+/// Lit segments for the digits 0 through 9.
+pub const DIGIT_SEGMENTS: [u8; 10] = [
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_E | SEG_F,
+    SEG_B | SEG_C,
+    SEG_A | SEG_B | SEG_G | SEG_E | SEG_D,
+    SEG_A | SEG_B | SEG_G | SEG_C | SEG_D,
+    SEG_F | SEG_G | SEG_B | SEG_C,
+    SEG_A | SEG_F | SEG_G | SEG_C | SEG_D,
+    SEG_A | SEG_F | SEG_G | SEG_E | SEG_C | SEG_D,
+    SEG_A | SEG_B | SEG_C,
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_E | SEG_F | SEG_G,
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_F | SEG_G,
+];
+/// Segments shown in place of a number too large for a [SegmentReadout].
+pub const DASH_SEGMENTS: u8 = SEG_G;
+/// Segments shown for a blank position, such as a leading zero.
+pub const BLANK_SEGMENTS: u8 = 0;
+
+/// Dimensions of a seven-segment digit. Segments are `thickness` pixels thick, and the corners between them are left
+/// blank so that each segment stands apart.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SegmentFont {
+    pub width: usize,
+    pub height: usize,
+    pub thickness: usize,
+}
+
+impl SegmentFont {
+    /// 16 x 32 digits, four fit side by side with room for units.
+    pub const SMALL: Self = Self::new(16, 32, 4);
+    /// 24 x 48 digits, for a three digit speed filling most of the display height.
+    pub const LARGE: Self = Self::new(24, 48, 6);
+
+    /// Panics if the digit is too small to hold its segments, or wider than a [DisplayArr].
+    pub const fn new(width: usize, height: usize, thickness: usize) -> Self {
+        if thickness == 0 || width < thickness * 3 || height < thickness * 5 {
+            panic!("segment digit is too small for its segment thickness");
+        }
+        if width > DisplayArr::WIDTH || height > DisplayArr::LEN {
+            panic!("segment digit does not fit in a DisplayArr");
+        }
+        Self {
+            width,
+            height,
+            thickness,
+        }
+    }
+
+    /// First row of the middle segment.
+    const fn middle(&self) -> usize {
+        (self.height - self.thickness) / 2
+    }
+
+    /// Gets row `idx` of a digit with `segments` lit, where 0 is the top row. The leftmost pixel is bit `self.width - 1`.
+    pub const fn row_bits(&self, segments: u8, idx: usize) -> u128 {
+        if idx >= self.height {
+            return 0;
+        }
+        let t = self.thickness;
+        let middle = self.middle();
+        let horizontal = ((1u128 << (self.width - 2 * t)) - 1) << t;
+        let left = ((1u128 << t) - 1) << (self.width - t);
+        let right = (1u128 << t) - 1;
+
+        let mut bits: u128 = 0;
+        if idx < t {
+            if segments & SEG_A != 0 {
+                bits |= horizontal;
+            }
+        } else if idx < middle {
+            if segments & SEG_F != 0 {
+                bits |= left;
+            }
+            if segments & SEG_B != 0 {
+                bits |= right;
+            }
+        } else if idx < middle + t {
+            if segments & SEG_G != 0 {
+                bits |= horizontal;
+            }
+        } else if idx < self.height - t {
+            if segments & SEG_E != 0 {
+                bits |= left;
+            }
+            if segments & SEG_C != 0 {
+                bits |= right;
+            }
+        } else if segments & SEG_D != 0 {
+            bits |= horizontal;
+        }
+        bits
+    }
+
+    /// Draws a digit with `segments` lit into `display` with its top-left corner at column `x`, row `y`. Unlike text,
+    /// the whole cell of the digit is overwritten, so unlit segments are cleared. Returns
+    /// [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if the digit would fall outside of `display`.
+    pub const fn draw_segments(
+        &self,
+        display: &mut DisplayArr,
+        segments: u8,
+        x: usize,
+        y: usize,
+    ) -> Result<(), DisplayArrErr> {
+        if x + self.width > DisplayArr::WIDTH {
+            return Err(DisplayArrErr::column_err(
+                DisplayArr::WIDTH - 1,
+                x + self.width - 1,
+            ));
+        }
+        if y + self.height > DisplayArr::LEN {
+            return Err(DisplayArrErr::row_err(
+                DisplayArr::LEN - 1,
+                y + self.height - 1,
+            ));
+        }
+
+        let shift = DisplayArr::WIDTH - x - self.width;
+        let mask = DisplayArr::row_mask(x, self.width);
+        let mut i: usize = 0;
+        while i < self.height {
+            let row = match display.row(y + i) {
+                Ok(row) => row,
+                Err(e) => return Err(e),
+            };
+            let bits = self.row_bits(segments, i) << shift;
+            if let Err(e) = display.set_row(y + i, (row & !mask) | bits) {
+                return Err(e);
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+
+    /// Draws `digit` (0 through 9) like [Self::draw_segments]. Other values draw a dash.
+    pub const fn draw_digit(
+        &self,
+        display: &mut DisplayArr,
+        digit: u8,
+        x: usize,
+        y: usize,
+    ) -> Result<(), DisplayArrErr> {
+        let segments = if digit < 10 {
+            DIGIT_SEGMENTS[digit as usize]
+        } else {
+            DASH_SEGMENTS
+        };
+        self.draw_segments(display, segments, x, y)
+    }
+}
+
+/// A right-aligned number of `N` seven-segment digits, which remembers what it last drew so that updates only redraw
+/// the digits which changed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SegmentReadout<const N: usize> {
+    pub font: SegmentFont,
+    /// Column of the left edge of the leftmost digit.
+    pub x: usize,
+    /// Row of the top edge of the digits.
+    pub y: usize,
+    /// Blank columns between two digits.
+    pub spacing: usize,
+    /// Segments currently drawn at each position, or [None] if the position has not been drawn yet.
+    shown: [Option<u8>; N],
+}
+
+impl<const N: usize> SegmentReadout<N> {
+    pub const fn new(font: SegmentFont, x: usize, y: usize, spacing: usize) -> Self {
+        Self {
+            font,
+            x,
+            y,
+            spacing,
+            shown: [None; N],
+        }
+    }
+
+    /// Total width of the readout in pixels.
+    pub const fn width(&self) -> usize {
+        if N == 0 {
+            0
+        } else {
+            N * self.font.width + (N - 1) * self.spacing
+        }
+    }
+
+    /// Gets the segments for each position of `value`, with leading zeros blanked. Values with more than `N` digits are
+    /// shown as dashes.
+    pub const fn segments_for(value: u32) -> [u8; N] {
+        let mut output = [BLANK_SEGMENTS; N];
+        let mut remaining = value;
+        let mut i = N;
+        while i > 0 {
+            i -= 1;
+            output[i] = DIGIT_SEGMENTS[(remaining % 10) as usize];
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+        if remaining != 0 {
+            output = [DASH_SEGMENTS; N];
+        }
+        output
+    }
+
+    /// Forgets what was drawn, so the next [Self::update] redraws every digit.
+    pub fn invalidate(&mut self) {
+        self.shown = [None; N];
+    }
+
+    /// Shows `value`, redrawing only the digits which differ from the last update. Returns the number of digits drawn.
+    pub fn update(&mut self, display: &mut DisplayArr, value: u32) -> Result<usize, DisplayArrErr> {
+        self.update_offset(display, value, 0, 0)
+    }
+
+    /// Shows `value` like [Self::update], with the readout moved right by `dx` columns and down by `dy` rows.
+    pub(crate) fn update_offset(
+        &mut self,
+        display: &mut DisplayArr,
+        value: u32,
+        dx: usize,
+        dy: usize,
+    ) -> Result<usize, DisplayArrErr> {
+        let segments = Self::segments_for(value);
+        let mut drawn: usize = 0;
+        for (i, &digit) in segments.iter().enumerate() {
+            if self.shown[i] == Some(digit) {
+                continue;
+            }
+            let x = dx + self.x + i * (self.font.width + self.spacing);
+            self.font.draw_segments(display, digit, x, dy + self.y)?;
+            self.shown[i] = Some(digit);
+            drawn += 1;
+        }
+        Ok(drawn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::Bencher;
+
+    #[test]
+    fn digit_rows() {
+        let font = SegmentFont::SMALL;
+        let eight = DIGIT_SEGMENTS[8];
+        let horizontal = 0b0000_1111_1111_0000u128;
+        let sides = 0b1111_0000_0000_1111u128;
+        assert_eq!(font.row_bits(eight, 0), horizontal);
+        assert_eq!(font.row_bits(eight, 3), horizontal);
+        assert_eq!(font.row_bits(eight, 4), sides);
+        assert_eq!(font.row_bits(eight, 13), sides);
+        assert_eq!(font.row_bits(eight, 14), horizontal);
+        assert_eq!(font.row_bits(eight, 17), horizontal);
+        assert_eq!(font.row_bits(eight, 18), sides);
+        assert_eq!(font.row_bits(eight, 27), sides);
+        assert_eq!(font.row_bits(eight, 28), horizontal);
+        assert_eq!(font.row_bits(eight, 31), horizontal);
+        assert_eq!(font.row_bits(eight, 32), 0);
+
+        let one = DIGIT_SEGMENTS[1];
+        for row in 0..32 {
+            assert_eq!(font.row_bits(one, row) & !0b1111, 0);
+        }
+        assert_eq!(font.row_bits(one, 0), 0);
+        assert_eq!(font.row_bits(one, 10), 0b1111);
+        // The sides of a zero break where the middle segment would be.
+        assert_eq!(font.row_bits(DIGIT_SEGMENTS[0], 15), 0);
+    }
+
+    #[test]
+    fn draw_overwrites_cell() {
+        let font = SegmentFont::LARGE;
+        let mut display = DisplayArr::new_full();
+        font.draw_digit(&mut display, 1, 10, 8).unwrap();
+        let mask = DisplayArr::row_mask(10, 24);
+        for row in 0..64 {
+            let value = display.row(row).unwrap();
+            if (8..56).contains(&row) {
+                assert_eq!(value & !mask, !mask);
+                assert_eq!(
+                    value & mask,
+                    font.row_bits(DIGIT_SEGMENTS[1], row - 8) << 94
+                );
+            } else {
+                assert_eq!(value, u128::MAX);
+            }
+        }
+
+        assert!(font.draw_digit(&mut display, 1, 105, 0).is_err());
+        assert!(font.draw_digit(&mut display, 1, 0, 17).is_err());
+    }
+
+    #[test]
+    fn readout_updates() {
+        let mut readout = SegmentReadout::<3>::new(SegmentFont::LARGE, 4, 8, 4);
+        assert_eq!(readout.width(), 80);
+        assert_eq!(
+            SegmentReadout::<3>::segments_for(7),
+            [BLANK_SEGMENTS, BLANK_SEGMENTS, DIGIT_SEGMENTS[7]]
+        );
+        assert_eq!(SegmentReadout::<3>::segments_for(1000), [DASH_SEGMENTS; 3]);
+
+        let mut display = DisplayArr::new();
+        assert_eq!(readout.update(&mut display, 88).unwrap(), 3);
+        assert_eq!(readout.update(&mut display, 88).unwrap(), 0);
+        assert_eq!(readout.update(&mut display, 89).unwrap(), 1);
+        assert_eq!(readout.update(&mut display, 109).unwrap(), 2);
+
+        let mut fresh = SegmentReadout::<3>::new(SegmentFont::LARGE, 4, 8, 4);
+        let mut expected = DisplayArr::new();
+        fresh.update(&mut expected, 109).unwrap();
+        assert_eq!(display, expected);
+
+        readout.invalidate();
+        assert_eq!(readout.update(&mut display, 109).unwrap(), 3);
+    }
+
+    #[bench]
+    fn bench_readout_update(b: &mut Bencher) {
+        let mut readout = SegmentReadout::<3>::new(SegmentFont::LARGE, 4, 8, 4);
+        let mut display = DisplayArr::new();
+        let mut speed = 0u32;
+        b.iter(|| {
+            speed = (speed + 1) % 160;
+            readout
+                .update(&mut display, test::black_box(speed))
+                .unwrap()
+        });
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::string::String;
+
+use crate::dirty::FrameDiff;
+use crate::image::{from_pbm, write_p1};
+use crate::newspeed::DisplayArr;
+
+/// Environment variable which makes [assert_snapshot] write goldens instead of checking them.
+const UPDATE_VAR: &str = "UPDATE_GOLDENS";
+const LIT: char = '#';
+const CLEAR: char = '.';
+/// Marks pixels which are lit, but clear in the golden.
+const ADDED: char = '+';
+/// Marks pixels which are clear, but lit in the golden.
+const REMOVED: char = '-';
+
+/// Whether goldens are being written rather than checked, which is only when [UPDATE_VAR] is `1`.
+fn updating() -> bool {
+    std::env::var_os(UPDATE_VAR).is_some_and(|value| value == "1")
+}
+
+/// Gets the path of the golden `name`, relative to `tests/golden`.
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name)
+}
+
+/// Whether `name` is stored as a netpbm bitmap rather than text.
+fn is_pbm(name: &str) -> bool {
+    name.ends_with(".pbm")
+}
+
+/// Renders `display` in the text golden format.
+pub(crate) fn to_text(display: &DisplayArr) -> String {
+    let mut output = String::new();
+    for y in 0..DisplayArr::LEN {
+        for x in 0..DisplayArr::WIDTH {
+            // Pixels within the display are always in bounds.
+            output.push(if display.bit(y, x).unwrap_or(false) {
+                LIT
+            } else {
+                CLEAR
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Parses the text golden format. Returns a description of the problem if `text` is not exactly one line of
+/// [DisplayArr::WIDTH] pixels for each row.
+pub(crate) fn from_text(text: &str) -> Result<DisplayArr, String> {
+    let mut display = DisplayArr::new();
+    let mut lines = 0;
+    for (y, line) in text.lines().enumerate() {
+        if y >= DisplayArr::LEN {
+            return Err(format!("more than {} rows", DisplayArr::LEN));
+        }
+        if line.chars().count() != DisplayArr::WIDTH {
+            return Err(format!(
+                "row {} is not {} pixels wide",
+                y,
+                DisplayArr::WIDTH
+            ));
+        }
+        for (x, pixel) in line.chars().enumerate() {
+            match pixel {
+                LIT => display.set_bit(y, x).map_err(|e| e.to_string())?,
+                CLEAR => {}
+                _ => return Err(format!("row {} column {} is {:?}", y, x, pixel)),
+            }
+        }
+        lines += 1;
+    }
+    if lines != DisplayArr::LEN {
+        return Err(format!("{} rows instead of {}", lines, DisplayArr::LEN));
+    }
+    Ok(display)
+}
+
+/// Renders `actual` over `expected`, marking pixels which were added with `+` and pixels which were removed with `-`.
+/// Rows with a difference are marked with `>` in the margin, and the changed area is summarised first.
+pub(crate) fn render_diff(expected: &DisplayArr, actual: &DisplayArr) -> String {
+    let diff = FrameDiff::new(expected, actual);
+    let changed = diff.changed();
+    let mut output = String::new();
+    match diff.bounds() {
+        Some(bounds) => {
+            let _ = writeln!(
+                output,
+                "{} pixels differ ({} added `{}`, {} removed `{}`), within {:?}",
+                (0..DisplayArr::LEN)
+                    .map(|y| changed.row(y).unwrap_or(0).count_ones())
+                    .sum::<u32>(),
+                (0..DisplayArr::LEN)
+                    .map(
+                        |y| (actual.row(y).unwrap_or(0) & !expected.row(y).unwrap_or(0))
+                            .count_ones()
+                    )
+                    .sum::<u32>(),
+                ADDED,
+                (0..DisplayArr::LEN)
+                    .map(
+                        |y| (expected.row(y).unwrap_or(0) & !actual.row(y).unwrap_or(0))
+                            .count_ones()
+                    )
+                    .sum::<u32>(),
+                REMOVED,
+                bounds,
+            );
+        }
+        None => {
+            let _ = writeln!(output, "no pixels differ");
+        }
+    }
+    for y in 0..DisplayArr::LEN {
+        let dirty = changed.row(y).unwrap_or(0) != 0;
+        let _ = write!(output, "{:>2}{} ", y, if dirty { '>' } else { ' ' });
+        for x in 0..DisplayArr::WIDTH {
+            let was = expected.bit(y, x).unwrap_or(false);
+            let is = actual.bit(y, x).unwrap_or(false);
+            output.push(match (was, is) {
+                (false, false) => CLEAR,
+                (true, true) => LIT,
+                (false, true) => ADDED,
+                (true, false) => REMOVED,
+            });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Checks `display` against the golden `name` under `tests/golden`, panicking with a visual diff if they differ. Goldens
+/// ending in `.pbm` are netpbm bitmaps, and the rest are text, one line per row with `#` for lit pixels and `.` for
+/// clear ones. With `UPDATE_GOLDENS=1` set, writes `display` as the golden instead, so the changes can be reviewed.
+#[track_caller]
+pub(crate) fn assert_snapshot(name: &str, display: &DisplayArr) {
+    let path = golden_path(name);
+    if updating() {
+        let mut contents = String::new();
+        if is_pbm(name) {
+            let _ = write_p1(display, &mut contents);
+        } else {
+            contents = to_text(display);
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("could not create the golden directory");
+        }
+        std::fs::write(&path, contents).expect("could not write the golden");
+        return;
+    }
+
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => panic!(
+            "golden {} could not be read ({}); run with {}=1 to create it from this output:\n{}",
+            path.display(),
+            e,
+            UPDATE_VAR,
+            to_text(display)
+        ),
+    };
+    let expected = if is_pbm(name) {
+        from_pbm(&data)
+            .unwrap_or_else(|e| panic!("golden {} is not a valid PBM: {}", path.display(), e))
+    } else {
+        let text = String::from_utf8_lossy(&data);
+        from_text(&text).unwrap_or_else(|e| panic!("golden {} is malformed: {}", path.display(), e))
+    };
+    if expected != *display {
+        panic!(
+            "output does not match golden {}; run with {}=1 to update it if the change is intended\n{}",
+            path.display(),
+            UPDATE_VAR,
+            render_diff(&expected, display)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_format() {
+        let mut display = DisplayArr::new();
+        display.set_bit(0, 0).unwrap();
+        display.set_bit(63, 127).unwrap();
+        let text = to_text(&display);
+        assert!(text.starts_with("#...."));
+        assert!(text.ends_with("...#\n"));
+        assert_eq!(from_text(&text).unwrap(), display);
+
+        assert!(from_text("#.\n").is_err());
+        assert!(from_text(&text.replacen('#', "x", 1)).is_err());
+        assert!(from_text(&text[..text.len() - 129]).is_err());
+        assert!(from_text(&format!("{}{}", text, &text[..129])).is_err());
+    }
+
+    #[test]
+    fn diffs() {
+        let mut expected = DisplayArr::new();
+        expected.set_bit(2, 3).unwrap();
+        expected.set_bit(2, 4).unwrap();
+        let mut actual = expected;
+        actual.clear_bit(2, 3).unwrap();
+        actual.set_bit(5, 10).unwrap();
+
+        let diff = render_diff(&expected, &actual);
+        let lines: Vec<&str> = diff.lines().collect();
+        assert_eq!(lines.len(), 65);
+        assert!(lines[0].starts_with("2 pixels differ (1 added `+`, 1 removed `-`)"));
+        assert!(lines[3].starts_with(" 2> ...-#."));
+        assert!(lines[6].starts_with(" 5> ..........+."));
+        assert!(lines[1].starts_with(" 0  ...."));
+        assert!(render_diff(&actual, &actual).starts_with("no pixels differ"));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match golden")]
+    fn mismatches_panic() {
+        if updating() {
+            panic!("does not match golden: skipped while updating goldens");
+        }
+        assert_snapshot("snapshot_blank.txt", &DisplayArr::new_full());
+    }
+
+    #[test]
+    fn matches() {
+        assert_snapshot("snapshot_blank.txt", &DisplayArr::new());
+        assert_snapshot("snapshot_blank.pbm", &DisplayArr::new());
+    }
+}
@@ -0,0 +1,257 @@
+use core::option::Option;
+use core::option::Option::None;
+use core::option::Option::Some;
+use core::simd::prelude::*;
+
+use crate::newspeed::{DisplayArr, Rect};
+use crate::text::place_bits;
+
+/// How the pixels of a [Sprite] combine with the pixels beneath it. Pixels outside of the sprite's mask are never
+/// changed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RasterOp {
+    /// Replaces the display with the sprite, clearing pixels which are off in the sprite.
+    Copy,
+    /// Turns on the pixels which are on in the sprite.
+    Or,
+    /// Turns off the pixels which are off in the sprite.
+    And,
+    /// Inverts the pixels which are on in the sprite.
+    Xor,
+    /// Turns off the pixels which are on in the sprite.
+    AndNot,
+}
+
+impl RasterOp {
+    /// Combines `dst` with `src` wherever `mask` is set.
+    pub const fn apply(self, dst: u64, src: u64, mask: u64) -> u64 {
+        let combined = match self {
+            RasterOp::Copy => src,
+            RasterOp::Or => dst | src,
+            RasterOp::And => dst & src,
+            RasterOp::Xor => dst ^ src,
+            RasterOp::AndNot => dst & !src,
+        };
+        (dst & !mask) | (combined & mask)
+    }
+
+    /// Combines every lane of `dst` with `src` wherever `mask` is set, like [Self::apply].
+    pub fn apply_lanes(self, dst: u64x64, src: u64x64, mask: u64x64) -> u64x64 {
+        let combined = match self {
+            RasterOp::Copy => src,
+            RasterOp::Or => dst | src,
+            RasterOp::And => dst & src,
+            RasterOp::Xor => dst ^ src,
+            RasterOp::AndNot => dst & !src,
+        };
+        (dst & !mask) | (combined & mask)
+    }
+}
+
+/// A bitmap of up to 64 x 64 pixels, such as a warning icon. Rows hold the leftmost pixel at bit `width - 1`, like
+/// glyph rows. Pixels are only drawn where the optional mask is set, so a sprite without a mask covers its whole box.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Sprite<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub rows: &'a [u64],
+    pub mask: Option<&'a [u64]>,
+}
+
+impl<'a> Sprite<'a> {
+    /// Panics if the sprite is larger than 64 x 64, or `rows` has fewer than `height` rows.
+    pub const fn new(width: usize, height: usize, rows: &'a [u64]) -> Self {
+        if width > 64 || height > DisplayArr::LEN {
+            panic!("sprites can be at most 64 x 64 pixels");
+        }
+        if rows.len() < height {
+            panic!("sprite has fewer rows than its height");
+        }
+        Self {
+            width,
+            height,
+            rows,
+            mask: None,
+        }
+    }
+
+    /// Only draws `self` where `mask` is set. Panics if `mask` has fewer than `height` rows.
+    pub const fn with_mask(self, mask: &'a [u64]) -> Self {
+        if mask.len() < self.height {
+            panic!("sprite mask has fewer rows than its height");
+        }
+        Self {
+            mask: Some(mask),
+            ..self
+        }
+    }
+
+    /// Bits of a row which lie within the sprite's width.
+    const fn width_mask(&self) -> u64 {
+        if self.width == 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.width) - 1
+        }
+    }
+
+    /// Gets the mask of row `idx`, where 0 is the top row.
+    pub const fn mask_row(&self, idx: usize) -> u64 {
+        match self.mask {
+            Some(mask) => mask[idx] & self.width_mask(),
+            None => self.width_mask(),
+        }
+    }
+
+    /// Draws `self` into `display` with its top-left corner at column `x`, row `y`, combining pixels with `op`. Parts
+    /// of the sprite which fall off the display are clipped. The sprite is placed into a whole frame of source and mask
+    /// rows first, so each half of `display` is updated with a single pass of lane operations.
+    pub fn blit(&self, display: &mut DisplayArr, x: isize, y: isize, op: RasterOp) {
+        let all = Rect::new(0, 0, DisplayArr::WIDTH, DisplayArr::LEN);
+        self.blit_clipped(display, x, y, op, &all);
+    }
+
+    /// Draws `self` like [Self::blit], but clipped to `clip` as well as to the display, so no pixel outside of `clip`
+    /// is written.
+    pub fn blit_clipped(
+        &self,
+        display: &mut DisplayArr,
+        x: isize,
+        y: isize,
+        op: RasterOp,
+        clip: &Rect,
+    ) {
+        let mut src = ([0u64; 64], [0u64; 64]);
+        let mut mask = ([0u64; 64], [0u64; 64]);
+        let mut touched = (false, false);
+        let clip_mask = DisplayArr::row_mask(clip.x, clip.width);
+        let bottom = clip.bottom().min(DisplayArr::LEN) as isize;
+
+        for i in 0..self.height {
+            let row = y + i as isize;
+            if row < clip.y as isize || row >= bottom {
+                continue;
+            }
+            let row = row as usize;
+            let row_mask = place_bits(self.mask_row(i) as u128, self.width, x) & clip_mask;
+            if row_mask == 0 {
+                continue;
+            }
+            let bits = place_bits((self.rows[i] & self.width_mask()) as u128, self.width, x);
+            (src.0[row], src.1[row]) = ((bits >> 64) as u64, bits as u64);
+            (mask.0[row], mask.1[row]) = ((row_mask >> 64) as u64, row_mask as u64);
+            touched.0 |= mask.0[row] != 0;
+            touched.1 |= mask.1[row] != 0;
+        }
+
+        let (left, right) = display.halves_mut();
+        if touched.0 {
+            *left = op.apply_lanes(*left, u64x64::from_array(src.0), u64x64::from_array(mask.0));
+        }
+        if touched.1 {
+            *right = op.apply_lanes(
+                *right,
+                u64x64::from_array(src.1),
+                u64x64::from_array(mask.1),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::Bencher;
+
+    const ARROW_ROWS: [u64; 5] = [0b00100, 0b01110, 0b11111, 0b00100, 0b00100];
+    const ARROW_MASK: [u64; 5] = [0b01110, 0b11111, 0b11111, 0b01110, 0b01110];
+    const ARROW: Sprite = Sprite::new(5, 5, &ARROW_ROWS);
+
+    #[test]
+    fn ops() {
+        let (dst, src, mask) = (0b1100, 0b1010, 0b0111);
+        assert_eq!(RasterOp::Copy.apply(dst, src, mask), 0b1010);
+        assert_eq!(RasterOp::Or.apply(dst, src, mask), 0b1110);
+        assert_eq!(RasterOp::And.apply(dst, src, mask), 0b1000);
+        assert_eq!(RasterOp::Xor.apply(dst, src, mask), 0b1110);
+        assert_eq!(RasterOp::AndNot.apply(dst, src, mask), 0b1100);
+
+        let lanes =
+            RasterOp::Xor.apply_lanes(u64x64::splat(dst), u64x64::splat(src), u64x64::splat(mask));
+        assert_eq!(lanes, u64x64::splat(0b1110));
+    }
+
+    #[test]
+    fn blit_straddles_halves() {
+        let mut display = DisplayArr::new();
+        ARROW.blit(&mut display, 62, 10, RasterOp::Or);
+        for (i, &row) in ARROW_ROWS.iter().enumerate() {
+            assert_eq!(display.row(10 + i).unwrap(), (row as u128) << 61);
+        }
+        assert_eq!(display.row(9).unwrap(), 0);
+        assert_eq!(display.row(15).unwrap(), 0);
+
+        // Xor-ing the same sprite again removes it.
+        ARROW.blit(&mut display, 62, 10, RasterOp::Xor);
+        assert_eq!(display, DisplayArr::new());
+    }
+
+    #[test]
+    fn blit_copy_and_masks() {
+        let mut display = DisplayArr::new_full();
+        ARROW.blit(&mut display, 0, 0, RasterOp::Copy);
+        assert_eq!(
+            display.row(0).unwrap(),
+            (0b00100u128 << 123) | (u128::MAX >> 5)
+        );
+        assert_eq!(display.row(5).unwrap(), u128::MAX);
+
+        let mut display = DisplayArr::new_full();
+        ARROW
+            .with_mask(&ARROW_MASK)
+            .blit(&mut display, 0, 0, RasterOp::Copy);
+        assert_eq!(display.row(0).unwrap(), !(0b01010u128 << 123));
+        assert_eq!(display.row(3).unwrap(), !(0b01010u128 << 123));
+
+        let mut display = DisplayArr::new_full();
+        ARROW.blit(&mut display, 0, 0, RasterOp::AndNot);
+        assert_eq!(display.row(2).unwrap(), u128::MAX >> 5);
+        ARROW.blit(&mut display, 0, 0, RasterOp::And);
+        assert_eq!(display.row(2).unwrap(), u128::MAX >> 5);
+        assert_eq!(display.row(1).unwrap(), !(0b11111u128 << 123));
+    }
+
+    #[test]
+    fn blit_clips() {
+        let mut display = DisplayArr::new();
+        ARROW.blit(&mut display, -2, -1, RasterOp::Or);
+        assert_eq!(display.row(0).unwrap(), 0b110u128 << 125);
+        assert_eq!(display.row(1).unwrap(), 0b111u128 << 125);
+        assert_eq!(display.row(4).unwrap(), 0);
+
+        let mut display = DisplayArr::new();
+        ARROW.blit(&mut display, 125, 61, RasterOp::Or);
+        assert_eq!(display.row(61).unwrap(), 0b001);
+        assert_eq!(display.row(62).unwrap(), 0b011);
+        assert_eq!(display.row(63).unwrap(), 0b111);
+
+        let mut display = DisplayArr::new();
+        ARROW.blit(&mut display, 128, 0, RasterOp::Or);
+        ARROW.blit(&mut display, 0, 64, RasterOp::Or);
+        ARROW.blit(&mut display, -5, -5, RasterOp::Or);
+        assert_eq!(display, DisplayArr::new());
+
+        // Pixels outside the sprite width are ignored.
+        let wide = [u64::MAX; 2];
+        let mut display = DisplayArr::new();
+        Sprite::new(3, 2, &wide).blit(&mut display, 0, 0, RasterOp::Or);
+        assert_eq!(display.row(1).unwrap(), 0b111u128 << 125);
+    }
+
+    #[bench]
+    fn bench_blit(b: &mut Bencher) {
+        let mut display = DisplayArr::new();
+        let sprite = ARROW.with_mask(&ARROW_MASK);
+        b.iter(|| sprite.blit(&mut display, test::black_box(62), 30, RasterOp::Xor));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::newspeed::Framebuffer;
+
+/// Rows in each page of controller memory.
+pub const PAGE_HEIGHT: usize = 8;
+/// Columns of memory in an SH1106, which is wider than the 128 column panels it drives.
+pub const SH1106_COLUMNS: usize = 132;
+/// First column of SH1106 memory which is visible on a 128 column panel.
+pub const SH1106_OFFSET: usize = 2;
+
+/// Gets the byte for each page of a column in the same form as [Framebuffer::column], where bit 0 of each byte is the
+/// top row of its page.
+const fn column_pages(column: u64) -> [u8; 8] {
+    column.reverse_bits().to_le_bytes()
+}
+
+/// Inverse of [column_pages].
+const fn pages_column(pages: [u8; 8]) -> u64 {
+    u64::from_le_bytes(pages).reverse_bits()
+}
+
+/// Converts `display` into the memory layout of an SSD1306: one page per 8 rows, each holding a byte per column, with
+/// the top row of the page in bit 0. The result can be sent to the controller as it is in horizontal addressing mode.
+/// `P` is the number of pages, `H / PAGE_HEIGHT`, so this does not compile if `H` is not a multiple of 8.
+pub fn to_pages<const W: usize, const H: usize, const S: usize, const P: usize>(
+    display: &Framebuffer<W, H, S>,
+) -> [[u8; W]; P] {
+    const { assert!(P * PAGE_HEIGHT == H, "P must be H / PAGE_HEIGHT") };
+    let mut pages = [[0u8; W]; P];
+    for (x, &column) in display.columns().iter().enumerate() {
+        let bytes = column_pages(column);
+        for (page, &byte) in pages.iter_mut().zip(bytes.iter()) {
+            page[x] = byte;
+        }
+    }
+    pages
+}
+
+/// Converts SSD1306 memory in the form made by [to_pages] back into a [Framebuffer]. Does not compile if `P` is not
+/// `H / PAGE_HEIGHT`.
+pub fn from_pages<const W: usize, const H: usize, const S: usize, const P: usize>(
+    pages: &[[u8; W]; P],
+) -> Framebuffer<W, H, S> {
+    const { assert!(P * PAGE_HEIGHT == H, "P must be H / PAGE_HEIGHT") };
+    let mut columns = [0u64; W];
+    for (x, column) in columns.iter_mut().enumerate() {
+        let mut bytes = [0u8; 8];
+        for (byte, page) in bytes.iter_mut().zip(pages.iter()) {
+            *byte = page[x];
+        }
+        *column = pages_column(bytes);
+    }
+    Framebuffer::new_from_columns(columns)
+}
+
+/// Converts `display` into the memory layout of an SH1106, which is that of an SSD1306 [SH1106_COLUMNS] wide, with
+/// the panel starting at column [SH1106_OFFSET]. Columns outside of the panel are left clear. Does not compile if `P`
+/// is not `H / PAGE_HEIGHT`, and panics if `display` does not fit in SH1106 memory.
+pub fn to_sh1106_pages<const W: usize, const H: usize, const S: usize, const P: usize>(
+    display: &Framebuffer<W, H, S>,
+) -> [[u8; SH1106_COLUMNS]; P] {
+    if W + SH1106_OFFSET > SH1106_COLUMNS {
+        panic!("framebuffer is wider than SH1106 memory");
+    }
+    let mut output = [[0u8; SH1106_COLUMNS]; P];
+    for (page, src) in output
+        .iter_mut()
+        .zip(to_pages::<W, H, S, P>(display).iter())
+    {
+        page[SH1106_OFFSET..SH1106_OFFSET + W].copy_from_slice(src);
+    }
+    output
+}
+
+/// Converts SH1106 memory in the form made by [to_sh1106_pages] back into a [Framebuffer], ignoring the columns
+/// outside of the panel. Does not compile if `P` is not `H / PAGE_HEIGHT`, and panics if `W` is wider than SH1106
+/// memory.
+pub fn from_sh1106_pages<const W: usize, const H: usize, const S: usize, const P: usize>(
+    pages: &[[u8; SH1106_COLUMNS]; P],
+) -> Framebuffer<W, H, S> {
+    if W + SH1106_OFFSET > SH1106_COLUMNS {
+        panic!("framebuffer is wider than SH1106 memory");
+    }
+    let mut visible = [[0u8; W]; P];
+    for (page, src) in visible.iter_mut().zip(pages.iter()) {
+        page.copy_from_slice(&src[SH1106_OFFSET..SH1106_OFFSET + W]);
+    }
+    from_pages(&visible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::{draw_rect, DrawMode};
+    use crate::newspeed::{DisplayArr, Rect};
+    use crate::text::draw_text;
+    use test::Bencher;
+
+    /// A one pixel border around the whole panel, the usual first frame when bringing up a display.
+    fn border_reference() -> [[u8; 128]; 8] {
+        let mut pages = [[0u8; 128]; 8];
+        for (i, page) in pages.iter_mut().enumerate() {
+            let edge = match i {
+                0 => 0x01,
+                7 => 0x80,
+                _ => 0x00,
+            };
+            page.fill(edge);
+            page[0] = 0xff;
+            page[127] = 0xff;
+        }
+        pages
+    }
+
+    #[test]
+    fn single_pixels() {
+        let mut display = DisplayArr::new();
+        display.set_bit(0, 0).unwrap();
+        display.set_bit(9, 64).unwrap();
+        display.set_bit(63, 127).unwrap();
+        let pages: [[u8; 128]; 8] = to_pages(&display);
+        assert_eq!(pages[0][0], 0x01);
+        assert_eq!(pages[1][64], 0x02);
+        assert_eq!(pages[7][127], 0x80);
+        let lit: usize = pages
+            .iter()
+            .flatten()
+            .map(|byte| byte.count_ones() as usize)
+            .sum();
+        assert_eq!(lit, 3);
+    }
+
+    #[test]
+    fn reference_frames() {
+        let mut border = DisplayArr::new();
+        draw_rect(&mut border, &Rect::new(0, 0, 128, 64), DrawMode::Set).unwrap();
+        assert_eq!(to_pages(&border), border_reference());
+        assert_eq!(from_pages(&border_reference()), border);
+        let sh1106: [[u8; SH1106_COLUMNS]; 8] = to_sh1106_pages(&border);
+        assert_eq!(sh1106[0][..4], [0x00, 0x00, 0xff, 0x01]);
+        assert_eq!(sh1106[3][128..], [0x00, 0xff, 0x00, 0x00]);
+
+        // Alternate rows light alternate bits of every byte.
+        let mut stripes = DisplayArr::new();
+        for row in (0..64).step_by(2) {
+            stripes.set_row(row, u128::MAX).unwrap();
+        }
+        assert_eq!(to_pages(&stripes), [[0x55; 128]; 8]);
+
+        // "HI" in the 3x5 font: H is columns 0 and 2 with a bar across row 2, and I is a bar down column 5.
+        let mut text = DisplayArr::new();
+        draw_text(&mut text, b"HI", 0, 1).unwrap();
+        let pages: [[u8; 128]; 8] = to_pages(&text);
+        assert_eq!(
+            pages[0][..8],
+            [0x3e, 0x08, 0x3e, 0x00, 0x22, 0x3e, 0x22, 0x00]
+        );
+        assert_eq!(pages[1], [0; 128]);
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut display = DisplayArr::new();
+        draw_text(&mut display, b"SPEED 88 MPH", 3, 20).unwrap();
+        draw_rect(&mut display, &Rect::new(1, 1, 120, 60), DrawMode::Xor).unwrap();
+        assert_eq!(from_pages(&to_pages::<128, 64, 2, 8>(&display)), display);
+
+        let sh1106: [[u8; SH1106_COLUMNS]; 8] = to_sh1106_pages(&display);
+        for page in sh1106.iter() {
+            assert_eq!(page[..SH1106_OFFSET], [0; SH1106_OFFSET]);
+            assert_eq!(page[SH1106_OFFSET + 128..], [0; 2]);
+        }
+        assert_eq!(from_sh1106_pages::<128, 64, 2, 8>(&sh1106), display);
+
+        let mut short = Framebuffer::<128, 32, 2>::new();
+        short.set_bit(31, 5).unwrap();
+        let pages: [[u8; 128]; 4] = to_pages(&short);
+        assert_eq!(pages.len(), 4);
+        assert_eq!(pages[3][5], 0x80);
+        assert_eq!(from_pages(&pages), short);
+    }
+
+    #[bench]
+    fn bench_to_pages(b: &mut Bencher) {
+        let mut display = DisplayArr::new();
+        draw_text(&mut display, b"SPEED 88 MPH", 3, 20).unwrap();
+        b.iter(|| to_pages::<128, 64, 2, 8>(&test::black_box(display)));
+    }
+}
@@ -0,0 +1,183 @@
+use core::option::Option;
+use core::option::Option::None;
+use core::option::Option::Some;
+
+use crate::newspeed::DisplayArr;
+
+/// What each new back buffer of a [SwapChain] starts as.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SwapMode {
+    /// Every frame starts blank, for screens which are redrawn from scratch.
+    Clear,
+    /// Every frame starts as a copy of the last presented frame, so only what changed needs drawing.
+    CopyForward,
+}
+
+/// A set of `N` [DisplayArr]s, so frames are drawn into a back buffer while the front buffer is being sent to the
+/// display, and the two never tear. Everything lives inline, so a swap chain can be a `static` on targets without a
+/// heap.
+///
+/// With 2 buffers, [Self::present] makes the back buffer the front straight away. With 3 or more, presented frames
+/// wait in a pending buffer until the display is ready for them and calls [Self::acquire], so drawing never waits on a
+/// transfer; if several frames are presented in between, only the newest is shown.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SwapChain<const N: usize> {
+    buffers: [DisplayArr; N],
+    front: usize,
+    back: usize,
+    pending: Option<usize>,
+    mode: SwapMode,
+}
+
+impl<const N: usize> SwapChain<N> {
+    /// Creates a swap chain where every buffer is blank. Panics if `N` is less than 2.
+    pub const fn new(mode: SwapMode) -> Self {
+        if N < 2 {
+            panic!("swap chains need at least 2 buffers");
+        }
+        Self {
+            buffers: [DisplayArr::new(); N],
+            front: 0,
+            back: 1,
+            pending: None,
+            mode,
+        }
+    }
+
+    pub const fn mode(&self) -> SwapMode {
+        self.mode
+    }
+
+    /// Changes what the next back buffer starts as. The current back buffer is left as it is.
+    pub const fn set_mode(&mut self, mode: SwapMode) {
+        self.mode = mode;
+    }
+
+    /// Gets the buffer being shown, which is the one to send to the display.
+    pub const fn front(&self) -> &DisplayArr {
+        &self.buffers[self.front]
+    }
+
+    /// Gets the buffer to draw the next frame into.
+    pub const fn back(&mut self) -> &mut DisplayArr {
+        &mut self.buffers[self.back]
+    }
+
+    /// Whether a presented frame is waiting for [Self::acquire].
+    pub const fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Finishes drawing the back buffer and moves on to a new one, started as [Self::mode] says. With 2 buffers the
+    /// finished frame becomes the front, otherwise it waits for [Self::acquire], replacing any frame which was already
+    /// waiting.
+    pub fn present(&mut self) {
+        let presented = self.back;
+        if N == 2 {
+            self.back = self.front;
+            self.front = presented;
+        } else {
+            // The next back buffer is any that is neither shown nor about to be.
+            let mut next = 0;
+            while next == self.front || next == presented {
+                next += 1;
+            }
+            self.pending = Some(presented);
+            self.back = next;
+        }
+        self.buffers[self.back] = match self.mode {
+            SwapMode::Clear => DisplayArr::new(),
+            SwapMode::CopyForward => self.buffers[presented],
+        };
+    }
+
+    /// Makes the newest presented frame the front buffer, if there is one waiting, and gets the front buffer. Call
+    /// this when the display is ready for a new frame, before sending [Self::front].
+    pub fn acquire(&mut self) -> &DisplayArr {
+        if let Some(pending) = self.pending.take() {
+            self.front = pending;
+        }
+        self.front()
+    }
+}
+
+impl<const N: usize> Default for SwapChain<N> {
+    fn default() -> Self {
+        Self::new(SwapMode::Clear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_buffering() {
+        let mut chain = SwapChain::<2>::new(SwapMode::Clear);
+        chain.back().set_bit(0, 0).unwrap();
+        assert_eq!(chain.front(), &DisplayArr::new());
+
+        chain.present();
+        assert!(chain.front().bit(0, 0).unwrap());
+        assert!(!chain.has_pending());
+        assert_eq!(chain.back(), &DisplayArr::new());
+
+        chain.back().set_bit(1, 1).unwrap();
+        chain.present();
+        assert!(chain.acquire().bit(1, 1).unwrap());
+        assert!(!chain.front().bit(0, 0).unwrap());
+    }
+
+    #[test]
+    fn triple_buffering() {
+        let mut chain = SwapChain::<3>::new(SwapMode::Clear);
+        chain.back().set_bit(0, 0).unwrap();
+        chain.present();
+        // The presented frame waits until the display acquires it.
+        assert!(chain.has_pending());
+        assert_eq!(chain.front(), &DisplayArr::new());
+
+        // Frames presented before the next acquire replace the waiting one.
+        chain.back().set_bit(1, 1).unwrap();
+        chain.present();
+        let front = *chain.acquire();
+        assert!(front.bit(1, 1).unwrap());
+        assert!(!front.bit(0, 0).unwrap());
+        assert!(!chain.has_pending());
+
+        // Acquiring with nothing waiting keeps showing the same frame.
+        assert_eq!(chain.acquire(), &front);
+
+        // Drawing never touches the front or pending buffers.
+        for i in 0..4 {
+            chain.back().set_row(i, u128::MAX).unwrap();
+            chain.present();
+            assert_eq!(chain.front(), &front);
+            assert_eq!(chain.back(), &DisplayArr::new());
+        }
+        assert_eq!(chain.acquire().row(3).unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn copy_forward() {
+        let mut chain = SwapChain::<3>::new(SwapMode::CopyForward);
+        chain.back().set_bit(0, 0).unwrap();
+        chain.present();
+        assert!(chain.back().bit(0, 0).unwrap());
+        chain.back().set_bit(1, 1).unwrap();
+        chain.present();
+        let front = *chain.acquire();
+        assert!(front.bit(0, 0).unwrap() && front.bit(1, 1).unwrap());
+
+        chain.set_mode(SwapMode::Clear);
+        assert_eq!(chain.mode(), SwapMode::Clear);
+        chain.present();
+        assert_eq!(chain.back(), &DisplayArr::new());
+    }
+
+    #[test]
+    fn in_a_static() {
+        static CHAIN: SwapChain<2> = SwapChain::new(SwapMode::CopyForward);
+        assert_eq!(CHAIN.front(), &DisplayArr::new());
+    }
+}
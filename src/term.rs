@@ -0,0 +1,150 @@
+use core::fmt::Formatter;
+use core::fmt::Write;
+use core::writeln;
+
+use crate::newspeed::Framebuffer;
+
+/// First Braille pattern character, with no dots raised.
+const BRAILLE_BLANK: u32 = 0x2800;
+/// Dot of a Braille cell for each row (0 to 3) and column (0 or 1) of its pixels.
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Gets a pixel of `display`, treating pixels past its edges as clear.
+fn lit<const W: usize, const H: usize, const S: usize>(
+    display: &Framebuffer<W, H, S>,
+    row: usize,
+    column: usize,
+) -> bool {
+    display.bit(row, column).unwrap_or(false)
+}
+
+/// Writes the top edge of a box around a `width` character wide image, or the bottom edge if `top` is false.
+fn write_edge(f: &mut Formatter<'_>, width: usize, top: bool) -> core::fmt::Result {
+    f.write_char(if top { '┌' } else { '└' })?;
+    for _ in 0..width {
+        f.write_char('─')?;
+    }
+    writeln!(f, "{}", if top { '┐' } else { '┘' })
+}
+
+/// Writes `lines` lines of `width` characters from `cell`, boxed in if the alternate flag (`{:#}`) is set, which shows
+/// where the edges of the display are.
+fn write_cells(
+    f: &mut Formatter<'_>,
+    width: usize,
+    lines: usize,
+    cell: impl Fn(usize, usize) -> char,
+) -> core::fmt::Result {
+    let framed = f.alternate();
+    if framed {
+        write_edge(f, width, true)?;
+    }
+    for line in 0..lines {
+        if framed {
+            f.write_char('│')?;
+        }
+        for column in 0..width {
+            f.write_char(cell(line, column))?;
+        }
+        if framed {
+            f.write_char('│')?;
+        }
+        writeln!(f)?;
+    }
+    if framed {
+        write_edge(f, width, false)?;
+    }
+    Ok(())
+}
+
+/// Shows a [Framebuffer] in a terminal as half-block characters (`▀`, `▄` and `█`), one column by two rows per
+/// character, so pixels come out roughly square. Use `{:#}` to draw a box around it.
+pub struct HalfBlocks<'a, const W: usize, const H: usize, const S: usize>(
+    pub &'a Framebuffer<W, H, S>,
+);
+
+impl<const W: usize, const H: usize, const S: usize> core::fmt::Display
+    for HalfBlocks<'_, W, H, S>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write_cells(f, W, H.div_ceil(2), |line, column| {
+            let top = lit(self.0, line * 2, column);
+            let bottom = lit(self.0, line * 2 + 1, column);
+            match (top, bottom) {
+                (false, false) => ' ',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (true, true) => '█',
+            }
+        })
+    }
+}
+
+/// Shows a [Framebuffer] in a terminal as Braille patterns, two columns by four rows per character, which fits a
+/// whole 128x64 frame into 64x16 characters. Use `{:#}` to draw a box around it.
+pub struct Braille<'a, const W: usize, const H: usize, const S: usize>(
+    pub &'a Framebuffer<W, H, S>,
+);
+
+impl<const W: usize, const H: usize, const S: usize> core::fmt::Display for Braille<'_, W, H, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write_cells(f, W.div_ceil(2), H.div_ceil(4), |line, column| {
+            let mut dots: u8 = 0;
+            for (dy, row_dots) in BRAILLE_DOTS.iter().enumerate() {
+                for (dx, &dot) in row_dots.iter().enumerate() {
+                    if lit(self.0, line * 4 + dy, column * 2 + dx) {
+                        dots |= dot;
+                    }
+                }
+            }
+            // Every value from the blank pattern up is a Braille character.
+            char::from_u32(BRAILLE_BLANK + dots as u32).unwrap_or(' ')
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newspeed::DisplayArr;
+    use crate::text::draw_text;
+
+    fn checker() -> Framebuffer<4, 4, 1> {
+        let mut display = Framebuffer::<4, 4, 1>::new();
+        display.set_row(0, 0b1100 << 124).unwrap();
+        display.set_row(1, 0b1010 << 124).unwrap();
+        display.set_row(3, 0b0001 << 124).unwrap();
+        display
+    }
+
+    #[test]
+    fn half_blocks() {
+        let display = checker();
+        assert_eq!(HalfBlocks(&display).to_string(), "█▀▄ \n   ▄\n");
+        assert_eq!(
+            format!("{:#}", HalfBlocks(&display)),
+            "┌────┐\n│█▀▄ │\n│   ▄│\n└────┘\n"
+        );
+
+        // An odd number of rows leaves the bottom half of the last line clear.
+        let mut odd = Framebuffer::<2, 3, 1>::new();
+        odd.set_row(2, 0b11 << 126).unwrap();
+        assert_eq!(HalfBlocks(&odd).to_string(), "  \n▀▀\n");
+    }
+
+    #[test]
+    fn braille() {
+        let display = checker();
+        // Left cell: (0,0), (0,1) and (1,0). Right cell: (1,2) and (3,3).
+        assert_eq!(Braille(&display).to_string(), "\u{280b}\u{2882}\n");
+
+        let mut text = DisplayArr::new();
+        draw_text(&mut text, b"HI", 0, 0).unwrap();
+        let rendered = format!("{:#}", Braille(&text));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 16 + 2);
+        assert!(lines.iter().all(|line| line.chars().count() == 64 + 2));
+        assert!(lines[1].starts_with("│\u{2867}"));
+        assert!(lines[3].ends_with("\u{2800}│"));
+    }
+}
@@ -0,0 +1,1339 @@
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+
+use crate::font::{FontGlyphs, MAX_GLYPH_HEIGHT};
+use crate::newspeed::{DisplayArr, DisplayArrErr, Rect};
+
+/// 5 x 3, with sign bit as visibility
+pub type StandardCharacter = u16;
+/// 5 x 5, with sign bit as visibility and 6 trailing unused 0s
+pub type WideCharacter = u32;
+/// 2 x 4, drawn on the bottom two rows of a line
+pub type SmallCharacter = u8;
+
+pub const STDCHAR_LENGTH: usize = 5;
+pub const STDCHAR_WIDTH: usize = 3;
+pub const WIDECHAR_LENGTH: usize = 5;
+pub const WIDECHAR_WIDTH: usize = 5;
+pub const SMALLCHAR_LENGTH: usize = 2;
+pub const SMALLCHAR_WIDTH: usize = 4;
+
+/// Height of a line of text in the built-in font, in pixels.
+pub const LINE_HEIGHT: usize = 5;
+/// Height of the tallest line of text a [TextLayout] can draw, in pixels.
+pub const MAX_LINE_HEIGHT: usize = MAX_GLYPH_HEIGHT;
+/// Blank columns between two adjacent characters.
+pub const LETTER_SPACING: usize = 1;
+
+pub const SPACE_PIXELS: StandardCharacter = 0b1000000000000000u16;
+pub const EXCL_PIXELS: StandardCharacter = 0b1010010010000010u16;
+pub const QUOTE_PIXELS: StandardCharacter = 0b1101101000000000u16;
+pub const HASH_PIXELS: WideCharacter = 0b10101011111010101111101010000000u32;
+pub const DOLLAR_PIXELS: WideCharacter = 0b10111010100011100010101110000000u32;
+
+pub const AT_PIXELS: WideCharacter = 0b11111110001101111011011111000000u32;
+pub const PERCENT_PIXELS: StandardCharacter = 0b1101001010100101u16;
+pub const CARET_PIXELS: StandardCharacter = 0b1010101101000000u16;
+pub const AMPERSAND_PIXELS: WideCharacter = 0b10110010000011011001001101000000u32;
+pub const ASTERISK_PIXELS: StandardCharacter = 0b1101010101000000u16;
+pub const LEFT_PAREN_PIXELS: StandardCharacter = 0b1001010010010001u16;
+pub const RIGHT_PAREN_PIXELS: StandardCharacter = 0b1100010010010100u16;
+pub const MINUS_PIXELS: StandardCharacter = 0b1000000111000000u16;
+pub const APOSTROPHE_PIXELS: StandardCharacter = 0b1010010000000000u16;
+pub const PLUS_PIXELS: StandardCharacter = 0b1000010111010000u16;
+pub const COLON_PIXELS: StandardCharacter = 0b1000010000010000u16;
+pub const SEMICOLON_PIXELS: StandardCharacter = 0b1000010000010100u16;
+pub const LESS_THAN_PIXELS: StandardCharacter = 0b1001010100010001u16;
+pub const EQUALS_PIXELS: StandardCharacter = 0b1000111000111000u16;
+pub const GREATER_THAN_PIXELS: StandardCharacter = 0b1100010001010100u16;
+pub const QUESTION_PIXELS: StandardCharacter = 0b1110001010000010u16;
+pub const LEFT_BRACKET_PIXELS: StandardCharacter = 0b1110100100100110u16;
+pub const BACKSLASH_PIXELS: StandardCharacter = 0b1100100010001001u16;
+pub const RIGHT_BRACKET_PIXELS: StandardCharacter = 0b1011001001001011u16;
+pub const UNDERSCORE_PIXELS: StandardCharacter = 0b1000000000000111u16;
+pub const BACKTICK_PIXELS: StandardCharacter = 0b1100010000000000u16;
+pub const LEFT_BRACE_PIXELS: StandardCharacter = 0b1011010100010011u16;
+pub const PIPE_PIXELS: StandardCharacter = 0b1010010010010010u16;
+pub const RIGHT_BRACE_PIXELS: StandardCharacter = 0b1110010001010110u16;
+pub const TILDE_PIXELS: WideCharacter = 0b10000001000101010001000000000000u32;
+pub const A_PIXELS: StandardCharacter = 0b1111101111101101u16;
+pub const B_PIXELS: StandardCharacter = 0b1110101110101110u16;
+pub const C_PIXELS: StandardCharacter = 0b1111100100100111u16;
+pub const D_PIXELS: StandardCharacter = 0b1110101101101110u16;
+pub const E_PIXELS: StandardCharacter = 0b1111100111100111u16;
+pub const F_PIXELS: StandardCharacter = 0b1111100111100100u16;
+pub const G_PIXELS: StandardCharacter = 0b1111100101101111u16;
+pub const H_PIXELS: StandardCharacter = 0b1101101111101101u16;
+pub const I_PIXELS: StandardCharacter = 0b1111010010010111u16;
+pub const J_PIXELS: StandardCharacter = 0b1111010010010110u16;
+pub const K_PIXELS: StandardCharacter = 0b1101101110101101u16;
+pub const L_PIXELS: StandardCharacter = 0b1100100100100111u16;
+pub const M_PIXELS: WideCharacter = 0b11101110101101011000110001000000u32;
+pub const N_PIXELS: StandardCharacter = 0b1110101101101101u16;
+pub const O_PIXELS: StandardCharacter = 0b1111101101101111u16;
+pub const P_PIXELS: StandardCharacter = 0b1111101111100100u16;
+pub const Q_PIXELS: StandardCharacter = 0b1111101101111001u16;
+pub const R_PIXELS: StandardCharacter = 0b1111101110101101u16;
+pub const S_PIXELS: StandardCharacter = 0b1111100111001111u16;
+pub const T_PIXELS: StandardCharacter = 0b1111010010010010u16;
+pub const U_PIXELS: StandardCharacter = 0b1101101101101111u16;
+pub const V_PIXELS: StandardCharacter = 0b1101101101101010u16;
+pub const W_PIXELS: WideCharacter = 0b11000110001101011010111011000000u32;
+pub const X_PIXELS: StandardCharacter = 0b1101101010101101u16;
+pub const Y_PIXELS: StandardCharacter = 0b1101101101010010u16;
+pub const Z_PIXELS: StandardCharacter = 0b1111001010100111u16;
+pub const LOWER_A_PIXELS: StandardCharacter = 0b1000000011101011u16;
+pub const LOWER_B_PIXELS: StandardCharacter = 0b1100100110101110u16;
+pub const LOWER_C_PIXELS: StandardCharacter = 0b1000000011100011u16;
+pub const LOWER_D_PIXELS: StandardCharacter = 0b1001001011101011u16;
+pub const LOWER_E_PIXELS: StandardCharacter = 0b1000010101110011u16;
+pub const LOWER_F_PIXELS: StandardCharacter = 0b1001010111010010u16;
+pub const LOWER_G_PIXELS: StandardCharacter = 0b1000011101011110u16;
+pub const LOWER_H_PIXELS: StandardCharacter = 0b1100100110101101u16;
+pub const LOWER_I_PIXELS: StandardCharacter = 0b1010000010010010u16;
+pub const LOWER_J_PIXELS: StandardCharacter = 0b1001000001101010u16;
+pub const LOWER_K_PIXELS: StandardCharacter = 0b1100100101110101u16;
+pub const LOWER_L_PIXELS: StandardCharacter = 0b1100100100100011u16;
+pub const LOWER_M_PIXELS: WideCharacter = 0b10000000000111101010110101000000u32;
+pub const LOWER_N_PIXELS: StandardCharacter = 0b1000000110101101u16;
+pub const LOWER_O_PIXELS: StandardCharacter = 0b1000000010101010u16;
+pub const LOWER_P_PIXELS: StandardCharacter = 0b1000110101110100u16;
+pub const LOWER_Q_PIXELS: StandardCharacter = 0b1000011101011001u16;
+pub const LOWER_R_PIXELS: StandardCharacter = 0b1000000101110100u16;
+pub const LOWER_S_PIXELS: StandardCharacter = 0b1000011100001110u16;
+pub const LOWER_T_PIXELS: StandardCharacter = 0b1010111010010001u16;
+pub const LOWER_U_PIXELS: StandardCharacter = 0b1000000101101011u16;
+pub const LOWER_V_PIXELS: StandardCharacter = 0b1000000101101010u16;
+pub const LOWER_W_PIXELS: WideCharacter = 0b10000000000100011010101010000000u32;
+pub const LOWER_X_PIXELS: StandardCharacter = 0b1000000101010101u16;
+pub const LOWER_Y_PIXELS: StandardCharacter = 0b1000101101011110u16;
+pub const LOWER_Z_PIXELS: StandardCharacter = 0b1000111010100111u16;
+pub const ZERO_PIXELS: StandardCharacter = 0b1111101101101111u16;
+pub const ONE_PIXELS: StandardCharacter = 0b1110010010010111u16;
+pub const TWO_PIXELS: StandardCharacter = 0b1111001011100111u16;
+pub const THREE_PIXELS: StandardCharacter = 0b1111001111001111u16;
+pub const FOUR_PIXELS: StandardCharacter = 0b1101101111001001u16;
+pub const FIVE_PIXELS: StandardCharacter = 0b1111100111001110u16;
+pub const SIX_PIXELS: StandardCharacter = 0b1111100111101111u16;
+pub const SEVEN_PIXELS: StandardCharacter = 0b1111001010010010u16;
+pub const EIGHT_PIXELS: StandardCharacter = 0b1111101111101111u16;
+pub const NINE_PIXELS: StandardCharacter = 0b1111101111001001u16;
+pub const SLASH_PIXELS: StandardCharacter = 0b1001001010100100u16;
+pub const DOT_PIXELS: SmallCharacter = 0b01100110u8;
+pub const COMMA_PIXELS: SmallCharacter = 0b01100010u8;
+pub const UNKNOWN_PIXELS: StandardCharacter = 0b1101010101010101u16;
+
+/// A single glyph of the built-in bitmap font, in one of the packed encodings.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisplayCharacter {
+    Standard(StandardCharacter),
+    Wide(WideCharacter),
+    Small(SmallCharacter),
+}
+
+impl DisplayCharacter {
+    pub const fn new_from_ascii(character: u8) -> Self {
+        match character {
+            b' ' => Self::Standard(SPACE_PIXELS),
+            b'!' => Self::Standard(EXCL_PIXELS),
+            b'"' => Self::Standard(QUOTE_PIXELS),
+            b'#' => Self::Wide(HASH_PIXELS),
+            b'$' => Self::Wide(DOLLAR_PIXELS),
+            b'%' => Self::Standard(PERCENT_PIXELS),
+            b'&' => Self::Wide(AMPERSAND_PIXELS),
+            b'\'' => Self::Standard(APOSTROPHE_PIXELS),
+            b'(' => Self::Standard(LEFT_PAREN_PIXELS),
+            b')' => Self::Standard(RIGHT_PAREN_PIXELS),
+            b'*' => Self::Standard(ASTERISK_PIXELS),
+            b'+' => Self::Standard(PLUS_PIXELS),
+            b',' => Self::Small(COMMA_PIXELS),
+            b'-' => Self::Standard(MINUS_PIXELS),
+            b'.' => Self::Small(DOT_PIXELS),
+            b'/' => Self::Standard(SLASH_PIXELS),
+            b'0' => Self::Standard(ZERO_PIXELS),
+            b'1' => Self::Standard(ONE_PIXELS),
+            b'2' => Self::Standard(TWO_PIXELS),
+            b'3' => Self::Standard(THREE_PIXELS),
+            b'4' => Self::Standard(FOUR_PIXELS),
+            b'5' => Self::Standard(FIVE_PIXELS),
+            b'6' => Self::Standard(SIX_PIXELS),
+            b'7' => Self::Standard(SEVEN_PIXELS),
+            b'8' => Self::Standard(EIGHT_PIXELS),
+            b'9' => Self::Standard(NINE_PIXELS),
+            b':' => Self::Standard(COLON_PIXELS),
+            b';' => Self::Standard(SEMICOLON_PIXELS),
+            b'<' => Self::Standard(LESS_THAN_PIXELS),
+            b'=' => Self::Standard(EQUALS_PIXELS),
+            b'>' => Self::Standard(GREATER_THAN_PIXELS),
+            b'?' => Self::Standard(QUESTION_PIXELS),
+            b'@' => Self::Wide(AT_PIXELS),
+            b'A' => Self::Standard(A_PIXELS),
+            b'B' => Self::Standard(B_PIXELS),
+            b'C' => Self::Standard(C_PIXELS),
+            b'D' => Self::Standard(D_PIXELS),
+            b'E' => Self::Standard(E_PIXELS),
+            b'F' => Self::Standard(F_PIXELS),
+            b'G' => Self::Standard(G_PIXELS),
+            b'H' => Self::Standard(H_PIXELS),
+            b'I' => Self::Standard(I_PIXELS),
+            b'J' => Self::Standard(J_PIXELS),
+            b'K' => Self::Standard(K_PIXELS),
+            b'L' => Self::Standard(L_PIXELS),
+            b'M' => Self::Wide(M_PIXELS),
+            b'N' => Self::Standard(N_PIXELS),
+            b'O' => Self::Standard(O_PIXELS),
+            b'P' => Self::Standard(P_PIXELS),
+            b'Q' => Self::Standard(Q_PIXELS),
+            b'R' => Self::Standard(R_PIXELS),
+            b'S' => Self::Standard(S_PIXELS),
+            b'T' => Self::Standard(T_PIXELS),
+            b'U' => Self::Standard(U_PIXELS),
+            b'V' => Self::Standard(V_PIXELS),
+            b'W' => Self::Wide(W_PIXELS),
+            b'X' => Self::Standard(X_PIXELS),
+            b'Y' => Self::Standard(Y_PIXELS),
+            b'Z' => Self::Standard(Z_PIXELS),
+            b'[' => Self::Standard(LEFT_BRACKET_PIXELS),
+            b'\\' => Self::Standard(BACKSLASH_PIXELS),
+            b']' => Self::Standard(RIGHT_BRACKET_PIXELS),
+            b'^' => Self::Standard(CARET_PIXELS),
+            b'_' => Self::Standard(UNDERSCORE_PIXELS),
+            b'`' => Self::Standard(BACKTICK_PIXELS),
+            b'a' => Self::Standard(LOWER_A_PIXELS),
+            b'b' => Self::Standard(LOWER_B_PIXELS),
+            b'c' => Self::Standard(LOWER_C_PIXELS),
+            b'd' => Self::Standard(LOWER_D_PIXELS),
+            b'e' => Self::Standard(LOWER_E_PIXELS),
+            b'f' => Self::Standard(LOWER_F_PIXELS),
+            b'g' => Self::Standard(LOWER_G_PIXELS),
+            b'h' => Self::Standard(LOWER_H_PIXELS),
+            b'i' => Self::Standard(LOWER_I_PIXELS),
+            b'j' => Self::Standard(LOWER_J_PIXELS),
+            b'k' => Self::Standard(LOWER_K_PIXELS),
+            b'l' => Self::Standard(LOWER_L_PIXELS),
+            b'm' => Self::Wide(LOWER_M_PIXELS),
+            b'n' => Self::Standard(LOWER_N_PIXELS),
+            b'o' => Self::Standard(LOWER_O_PIXELS),
+            b'p' => Self::Standard(LOWER_P_PIXELS),
+            b'q' => Self::Standard(LOWER_Q_PIXELS),
+            b'r' => Self::Standard(LOWER_R_PIXELS),
+            b's' => Self::Standard(LOWER_S_PIXELS),
+            b't' => Self::Standard(LOWER_T_PIXELS),
+            b'u' => Self::Standard(LOWER_U_PIXELS),
+            b'v' => Self::Standard(LOWER_V_PIXELS),
+            b'w' => Self::Wide(LOWER_W_PIXELS),
+            b'x' => Self::Standard(LOWER_X_PIXELS),
+            b'y' => Self::Standard(LOWER_Y_PIXELS),
+            b'z' => Self::Standard(LOWER_Z_PIXELS),
+            b'{' => Self::Standard(LEFT_BRACE_PIXELS),
+            b'|' => Self::Standard(PIPE_PIXELS),
+            b'}' => Self::Standard(RIGHT_BRACE_PIXELS),
+            b'~' => Self::Wide(TILDE_PIXELS),
+            _ => Self::Standard(UNKNOWN_PIXELS),
+        }
+    }
+
+    /// Width of the glyph in pixels, not including [LETTER_SPACING].
+    pub const fn width(&self) -> usize {
+        match self {
+            Self::Standard(_) => STDCHAR_WIDTH,
+            Self::Wide(_) => WIDECHAR_WIDTH,
+            Self::Small(_) => SMALLCHAR_WIDTH,
+        }
+    }
+
+    /// Height of the glyph in pixels. Always <= [LINE_HEIGHT].
+    pub const fn height(&self) -> usize {
+        match self {
+            Self::Standard(_) => STDCHAR_LENGTH,
+            Self::Wide(_) => WIDECHAR_LENGTH,
+            Self::Small(_) => SMALLCHAR_LENGTH,
+        }
+    }
+
+    /// Gets row `idx` of the glyph, where 0 is the top row of the line. The leftmost pixel is bit `self.width() - 1`.
+    /// Glyphs shorter than [LINE_HEIGHT] sit on the bottom of the line, so their upper rows are blank.
+    pub const fn row_bits(&self, idx: usize) -> u8 {
+        let top = LINE_HEIGHT - self.height();
+        if idx < top || idx >= LINE_HEIGHT {
+            return 0;
+        }
+        let i = idx - top;
+        let width = self.width();
+        let mask = (1u32 << width) - 1;
+
+        let bits = match *self {
+            Self::Standard(data) => (data as u32) >> ((STDCHAR_LENGTH - (i + 1)) * STDCHAR_WIDTH),
+            Self::Wide(data) => data >> (((WIDECHAR_LENGTH - (i + 1)) * WIDECHAR_WIDTH) + 6),
+            Self::Small(data) => (data as u32) >> ((SMALLCHAR_LENGTH - (i + 1)) * SMALLCHAR_WIDTH),
+        };
+        (bits & mask) as u8
+    }
+
+    /// Gets the number of blank columns on the left of the glyph, and the width of the columns between its leftmost and
+    /// rightmost lit pixels. Blank glyphs (such as space) have an inked width of 0.
+    pub const fn ink_bounds(&self) -> (usize, usize) {
+        let mut mask: u8 = 0;
+        let mut i: usize = 0;
+        while i < LINE_HEIGHT {
+            mask |= self.row_bits(i);
+            i += 1;
+        }
+        if mask == 0 {
+            return (0, 0);
+        }
+        let highest = 7 - mask.leading_zeros() as usize;
+        let lowest = mask.trailing_zeros() as usize;
+        (self.width() - 1 - highest, highest + 1 - lowest)
+    }
+
+    /// Draws the glyph into `display` with its top-left corner at column `x`, row `y`. Returns
+    /// [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if any part of the glyph would fall outside of `display`.
+    pub const fn draw(
+        &self,
+        display: &mut DisplayArr,
+        x: usize,
+        y: usize,
+    ) -> Result<(), DisplayArrErr> {
+        self.draw_scaled(display, x, y, 1)
+    }
+
+    /// Draws the glyph like [Self::draw], but `scale` times larger, so that each pixel becomes a `scale` x `scale`
+    /// square. Panics if `scale` is 0.
+    pub const fn draw_scaled(
+        &self,
+        display: &mut DisplayArr,
+        x: usize,
+        y: usize,
+        scale: usize,
+    ) -> Result<(), DisplayArrErr> {
+        if scale == 0 {
+            panic!("glyphs cannot be scaled by 0");
+        }
+        let width = self.width() * scale;
+        if x + width > DisplayArr::WIDTH {
+            return Err(DisplayArrErr::column_err(
+                DisplayArr::WIDTH - 1,
+                x + width - 1,
+            ));
+        }
+        if y + LINE_HEIGHT * scale > DisplayArr::LEN {
+            return Err(DisplayArrErr::row_err(
+                DisplayArr::LEN - 1,
+                y + LINE_HEIGHT * scale - 1,
+            ));
+        }
+
+        let mut i: usize = 0;
+        while i < LINE_HEIGHT {
+            let bits = scale_bits(self.row_bits(i) as u128, self.width(), scale);
+            if bits != 0 {
+                let row = place_bits(bits, width, x as isize);
+                let mut k: usize = 0;
+                while k < scale {
+                    if let Err(e) = display.oreq_row(y + i * scale + k, row) {
+                        return Err(e);
+                    }
+                    k += 1;
+                }
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Stretches the low `width` bits of `bits` so that each becomes `scale` bits. Bits which would land past the top of a
+/// [u128] are dropped, so `width * scale` should be at most 128.
+pub const fn scale_bits(bits: u128, width: usize, scale: usize) -> u128 {
+    if scale == 1 {
+        return bits & (u128::MAX >> (128 - width));
+    }
+    let fill = if scale >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << scale) - 1
+    };
+    let mut output: u128 = 0;
+    let mut i: usize = 0;
+    while i < width && i * scale < 128 {
+        if bits & (1u128 << i) != 0 {
+            output |= fill << (i * scale);
+        }
+        i += 1;
+    }
+    output
+}
+
+/// Places the low `width` bits of `bits` in a row, with the highest of them at column `x`. Columns which fall outside
+/// of the row are dropped.
+pub(crate) const fn place_bits(bits: u128, width: usize, x: isize) -> u128 {
+    let shift = 128 - x - width as isize;
+    if shift >= 128 || shift <= -(width as isize) {
+        0
+    } else if shift >= 0 {
+        bits << shift
+    } else {
+        bits >> -shift
+    }
+}
+
+/// The glyphs a [TextLayout] draws characters with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GlyphSource<'a> {
+    /// The built-in font of [DisplayCharacter]s, [LINE_HEIGHT] pixels tall.
+    Builtin,
+    /// A font imported from a PSF or BDF file. See [crate::font::BitmapFont::layout].
+    Imported(FontGlyphs<'a>),
+}
+
+impl GlyphSource<'_> {
+    /// Height of a line of text, in pixels.
+    pub const fn line_height(&self) -> usize {
+        match self {
+            Self::Builtin => LINE_HEIGHT,
+            Self::Imported(font) => font.height,
+        }
+    }
+
+    /// Width of the cell `character` is drawn in.
+    pub const fn cell_width(&self, character: u8) -> usize {
+        match self {
+            Self::Builtin => DisplayCharacter::new_from_ascii(character).width(),
+            Self::Imported(font) => font.width,
+        }
+    }
+
+    /// Columns from the start of the cell of `character` to the start of the next cell, before any letter spacing or
+    /// kerning.
+    pub const fn cell_advance(&self, character: u8) -> usize {
+        match self {
+            Self::Builtin => DisplayCharacter::new_from_ascii(character).width(),
+            Self::Imported(font) => font.advance(character),
+        }
+    }
+
+    /// Gets row `idx` of `character`, where 0 is the top row of the line. The leftmost pixel is bit
+    /// `self.cell_width(character) - 1`.
+    pub const fn row_bits(&self, character: u8, idx: usize) -> u32 {
+        match self {
+            Self::Builtin => DisplayCharacter::new_from_ascii(character).row_bits(idx) as u32,
+            Self::Imported(font) => font.row_bits(character, idx),
+        }
+    }
+
+    /// Gets the number of blank columns on the left of `character`, and the width of its lit columns. See
+    /// [DisplayCharacter::ink_bounds].
+    pub const fn ink_bounds(&self, character: u8) -> (usize, usize) {
+        match self {
+            Self::Builtin => DisplayCharacter::new_from_ascii(character).ink_bounds(),
+            Self::Imported(font) => font.ink_bounds(character),
+        }
+    }
+}
+
+/// Moves the character `right` by `adjust` columns when it directly follows the character `left`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KerningPair {
+    pub left: u8,
+    pub right: u8,
+    pub adjust: i8,
+}
+
+impl KerningPair {
+    pub const fn new(left: u8, right: u8, adjust: i8) -> Self {
+        Self {
+            left,
+            right,
+            adjust,
+        }
+    }
+}
+
+/// Pairs which can be tightened by a column without any of their pixels touching.
+pub const DEFAULT_KERNING: &[KerningPair] = &[
+    KerningPair::new(b'L', b'T', -1),
+    KerningPair::new(b'L', b'Y', -1),
+    KerningPair::new(b'T', b'.', -1),
+    KerningPair::new(b'T', b',', -1),
+    KerningPair::new(b'F', b'.', -1),
+    KerningPair::new(b'F', b',', -1),
+    KerningPair::new(b'P', b'.', -1),
+    KerningPair::new(b'P', b',', -1),
+    KerningPair::new(b'Y', b'.', -1),
+    KerningPair::new(b'Y', b',', -1),
+    KerningPair::new(b'7', b'.', -1),
+    KerningPair::new(b'7', b',', -1),
+    KerningPair::new(b'/', b'/', -1),
+];
+
+/// Size of a piece of text, in pixels.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextMetrics {
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Rules for placing characters next to each other.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextLayout<'a> {
+    /// Blank columns between two adjacent characters, before kerning.
+    pub letter_spacing: usize,
+    /// If true, each character takes up only the columns between its leftmost and rightmost lit pixels. Otherwise, each
+    /// character takes up its full cell, and is advanced past by [GlyphSource::cell_advance].
+    pub proportional: bool,
+    /// Width of blank characters (such as space) when [Self::proportional] is set.
+    pub space_width: usize,
+    pub kerning: &'a [KerningPair],
+    pub glyphs: GlyphSource<'a>,
+}
+
+impl<'a> TextLayout<'a> {
+    /// Every character takes up its full cell, with no kerning. This is what [draw_text] uses.
+    pub const MONOSPACE: TextLayout<'static> = TextLayout {
+        letter_spacing: LETTER_SPACING,
+        proportional: false,
+        space_width: STDCHAR_WIDTH,
+        kerning: &[],
+        glyphs: GlyphSource::Builtin,
+    };
+    /// Characters take up only their lit columns, with [DEFAULT_KERNING] applied.
+    pub const PROPORTIONAL: TextLayout<'static> = TextLayout {
+        letter_spacing: LETTER_SPACING,
+        proportional: true,
+        space_width: 2,
+        kerning: DEFAULT_KERNING,
+        glyphs: GlyphSource::Builtin,
+    };
+
+    /// Height of a line of text, in pixels.
+    pub const fn line_height(&self) -> usize {
+        self.glyphs.line_height()
+    }
+
+    /// Gets the blank columns skipped on the left of `character`, and the number of columns it takes up.
+    const fn glyph_box(&self, character: u8) -> (usize, usize) {
+        if !self.proportional {
+            return (0, self.glyphs.cell_width(character));
+        }
+        match self.glyphs.ink_bounds(character) {
+            (_, 0) => (0, self.space_width),
+            bounds => bounds,
+        }
+    }
+
+    /// Gets the number of columns `character` takes up, not including spacing.
+    pub const fn glyph_width(&self, character: u8) -> usize {
+        self.glyph_box(character).1
+    }
+
+    /// Gets the kerning adjustment between `left` and the character `right` following it.
+    pub const fn kerning(&self, left: u8, right: u8) -> isize {
+        let mut i: usize = 0;
+        while i < self.kerning.len() {
+            let pair = self.kerning[i];
+            if pair.left == left && pair.right == right {
+                return pair.adjust as isize;
+            }
+            i += 1;
+        }
+        0
+    }
+
+    /// Gets the distance from the start of `character` to the start of the character `next` following it.
+    pub const fn advance(&self, character: u8, next: u8) -> usize {
+        let width = if self.proportional {
+            self.glyph_width(character)
+        } else {
+            self.glyphs.cell_advance(character)
+        };
+        let advance = (width + self.letter_spacing) as isize + self.kerning(character, next);
+        if advance < 0 {
+            0
+        } else {
+            advance as usize
+        }
+    }
+
+    /// Measures `text` without drawing it.
+    pub const fn measure(&self, text: &[u8]) -> TextMetrics {
+        if text.is_empty() {
+            return TextMetrics {
+                width: 0,
+                height: 0,
+            };
+        }
+
+        let mut cursor: usize = 0;
+        let mut width: usize = 0;
+        let mut i: usize = 0;
+        while i < text.len() {
+            let end = cursor + self.glyph_width(text[i]);
+            if end > width {
+                width = end;
+            }
+            if i + 1 < text.len() {
+                cursor += self.advance(text[i], text[i + 1]);
+            }
+            i += 1;
+        }
+        TextMetrics {
+            width,
+            height: self.line_height(),
+        }
+    }
+
+    /// Measures `text` without drawing it, as if it were drawn `scale` times larger.
+    pub const fn measure_scaled(&self, text: &[u8], scale: usize) -> TextMetrics {
+        let metrics = self.measure(text);
+        TextMetrics {
+            width: metrics.width * scale,
+            height: metrics.height * scale,
+        }
+    }
+
+    /// Draws `text` into `display` with the top-left corner of the first character at column `x`, row `y`.
+    /// Returns the column at which a following character would be drawn. If the text does not fit, nothing is drawn and
+    /// [DisplayArrErr::TextOverflowError] or [DisplayArrErr::InvalidRowError] is returned.
+    pub const fn draw(
+        &self,
+        display: &mut DisplayArr,
+        text: &[u8],
+        x: usize,
+        y: usize,
+    ) -> Result<usize, DisplayArrErr> {
+        self.draw_scaled(display, text, x, y, 1)
+    }
+
+    /// Draws `text` like [Self::draw], but `scale` times larger, spacing included. Panics if `scale` is 0.
+    pub const fn draw_scaled(
+        &self,
+        display: &mut DisplayArr,
+        text: &[u8],
+        x: usize,
+        y: usize,
+        scale: usize,
+    ) -> Result<usize, DisplayArrErr> {
+        if scale == 0 {
+            panic!("text cannot be scaled by 0");
+        }
+        let metrics = self.measure_scaled(text, scale);
+        if metrics.width == 0 {
+            return Ok(x);
+        }
+        if x + metrics.width > DisplayArr::WIDTH {
+            return Err(DisplayArrErr::text_overflow_err(
+                DisplayArr::WIDTH - 1,
+                x + metrics.width - 1,
+            ));
+        }
+        if y + metrics.height > DisplayArr::LEN {
+            return Err(DisplayArrErr::row_err(
+                DisplayArr::LEN - 1,
+                y + metrics.height - 1,
+            ));
+        }
+
+        let unscaled_width = metrics.width / scale;
+        let rows = self.render_line(text, 0);
+        let mut i: usize = 0;
+        while i < self.line_height() {
+            if rows[i] != 0 {
+                let bits = scale_bits(rows[i] >> (128 - unscaled_width), unscaled_width, scale);
+                let row = place_bits(bits, metrics.width, x as isize);
+                let mut k: usize = 0;
+                while k < scale {
+                    if let Err(e) = display.oreq_row(y + i * scale + k, row) {
+                        return Err(e);
+                    }
+                    k += 1;
+                }
+            }
+            i += 1;
+        }
+        Ok(x + metrics.width + self.letter_spacing * scale)
+    }
+
+    /// Renders `text` as a single line of rows, with the first character starting at column `x`. Columns which fall
+    /// outside of the rows (including negative columns) are dropped, and rows past [Self::line_height] are blank.
+    pub const fn render_line(&self, text: &[u8], x: isize) -> [u128; MAX_LINE_HEIGHT] {
+        let mut rows = [0u128; MAX_LINE_HEIGHT];
+        let mut cursor = x;
+        let mut i: usize = 0;
+        while i < text.len() {
+            let character = text[i];
+            let (skip, width) = self.glyph_box(character);
+            let ink = if self.proportional {
+                self.glyphs.ink_bounds(character).1
+            } else {
+                width
+            };
+            if ink > 0 {
+                let trailing = self.glyphs.cell_width(character) - skip - ink;
+                let mut row: usize = 0;
+                while row < self.line_height() {
+                    let bits = (self.glyphs.row_bits(character, row) >> trailing) as u128;
+                    rows[row] |= place_bits(bits, ink, cursor);
+                    row += 1;
+                }
+            }
+            if i + 1 < text.len() {
+                cursor += self.advance(text[i], text[i + 1]) as isize;
+            }
+            i += 1;
+        }
+        rows
+    }
+}
+
+/// Horizontal placement of each line of text within a [TextBox].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// What a [TextBox] does with text that does not fit in it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overflow {
+    /// Draw as much as fits, cutting characters off at the edges of the box.
+    Clip,
+    /// Only draw whole lines, and end the last one with `...` if anything was left out.
+    Ellipsis,
+}
+
+/// Text used to mark truncated lines for [Overflow::Ellipsis].
+pub const ELLIPSIS: &[u8] = b"...";
+
+/// A rectangle of a [DisplayArr] which text is drawn into, one line after another.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextBox<'a> {
+    pub bounds: Rect,
+    pub layout: TextLayout<'a>,
+    pub alignment: Alignment,
+    pub overflow: Overflow,
+    /// If true, lines are broken between words (or inside words too long for a whole line) to fit the width of the box.
+    /// Otherwise, lines are only broken at `\n`.
+    pub wrap: bool,
+    /// Blank rows between two lines.
+    pub line_spacing: usize,
+    /// If true, the box is cleared before the text is drawn.
+    pub clear: bool,
+}
+
+impl<'a> TextBox<'a> {
+    /// Creates a [TextBox] which wraps left-aligned, proportional text and clips it at the edges of `bounds`.
+    pub const fn new(bounds: Rect) -> TextBox<'static> {
+        TextBox {
+            bounds,
+            layout: TextLayout::PROPORTIONAL,
+            alignment: Alignment::Left,
+            overflow: Overflow::Clip,
+            wrap: true,
+            line_spacing: 1,
+            clear: false,
+        }
+    }
+
+    /// Splits `text` into the lines it would be drawn as, ignoring the height of the box.
+    pub fn lines<'t>(&self, text: &'t [u8]) -> Lines<'a, 't> {
+        Lines {
+            text,
+            layout: self.layout,
+            max_width: self.bounds.width,
+            wrap: self.wrap,
+        }
+    }
+
+    /// Gets the column at which a line `width` pixels wide starts.
+    fn line_x(&self, width: usize) -> isize {
+        let free = self.bounds.width as isize - width as isize;
+        self.bounds.x as isize
+            + match self.alignment {
+                Alignment::Left => 0,
+                Alignment::Center => free.div_euclid(2),
+                Alignment::Right => free,
+            }
+    }
+
+    /// Renders `line` with as many of its characters as fit before an [ELLIPSIS].
+    fn render_truncated(&self, line: &[u8]) -> [u128; MAX_LINE_HEIGHT] {
+        let ellipsis_width = self.layout.measure(ELLIPSIS).width;
+        let gap = |prefix: &[u8]| match prefix.last() {
+            Some(&last) => self
+                .layout
+                .advance(last, ELLIPSIS[0])
+                .saturating_sub(self.layout.glyph_width(last)),
+            None => 0,
+        };
+
+        let mut prefix: &[u8] = &[];
+        for end in (0..=line.len()).rev() {
+            let candidate = trim_end(&line[..end]);
+            let width = self.layout.measure(candidate).width + gap(candidate) + ellipsis_width;
+            if width <= self.bounds.width || candidate.is_empty() {
+                prefix = candidate;
+                break;
+            }
+        }
+
+        let prefix_width = self.layout.measure(prefix).width;
+        let x = self.line_x(prefix_width + gap(prefix) + ellipsis_width);
+        let mut rows = self.layout.render_line(prefix, x);
+        let ellipsis_rows = self
+            .layout
+            .render_line(ELLIPSIS, x + (prefix_width + gap(prefix)) as isize);
+        for (row, ellipsis_row) in rows.iter_mut().zip(ellipsis_rows) {
+            *row |= ellipsis_row;
+        }
+        rows
+    }
+
+    /// Draws `text` into `display`, clipped to [Self::bounds]. Returns the number of lines drawn, or
+    /// [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if the box does not fit in `display`.
+    pub fn draw(&self, display: &mut DisplayArr, text: &[u8]) -> Result<usize, DisplayArrErr> {
+        DisplayArr::check_rect(&self.bounds)?;
+        let mask = DisplayArr::row_mask(self.bounds.x, self.bounds.width);
+        let bottom = self.bounds.bottom();
+
+        if self.clear {
+            for row in self.bounds.y..bottom {
+                display.set_row(row, display.row(row)? & !mask)?;
+            }
+        }
+        if self.bounds.is_empty() {
+            return Ok(0);
+        }
+
+        let line_height = self.layout.line_height();
+        let pitch = line_height + self.line_spacing;
+        let mut lines = self.lines(text).peekable();
+        let mut top = self.bounds.y;
+        let mut drawn: usize = 0;
+
+        while let Some(line) = lines.next() {
+            if top >= bottom {
+                break;
+            }
+
+            let rows = match self.overflow {
+                Overflow::Clip => self
+                    .layout
+                    .render_line(line, self.line_x(self.layout.measure(line).width)),
+                Overflow::Ellipsis => {
+                    if top + line_height > bottom {
+                        break;
+                    }
+                    let last = top + pitch + line_height > bottom;
+                    let width = self.layout.measure(line).width;
+                    if (last && lines.peek().is_some()) || width > self.bounds.width {
+                        self.render_truncated(line)
+                    } else {
+                        self.layout.render_line(line, self.line_x(width))
+                    }
+                }
+            };
+
+            for (i, row) in rows[..line_height].iter().enumerate() {
+                if top + i >= bottom {
+                    break;
+                }
+                if row & mask != 0 {
+                    display.oreq_row(top + i, row & mask)?;
+                }
+            }
+            drawn += 1;
+            top += pitch;
+        }
+        Ok(drawn)
+    }
+}
+
+/// Removes trailing spaces from `text`.
+fn trim_end(text: &[u8]) -> &[u8] {
+    let mut end = text.len();
+    while end > 0 && text[end - 1] == b' ' {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Iterator over the lines of text in a [TextBox]. See [TextBox::lines].
+pub struct Lines<'a, 't> {
+    text: &'t [u8],
+    layout: TextLayout<'a>,
+    max_width: usize,
+    wrap: bool,
+}
+
+impl<'a, 't> Iterator for Lines<'a, 't> {
+    type Item = &'t [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.text.is_empty() {
+            return None;
+        }
+
+        let end = self
+            .text
+            .iter()
+            .position(|&c| c == b'\n')
+            .unwrap_or(self.text.len());
+        let candidate = &self.text[..end];
+        if !self.wrap || self.layout.measure(candidate).width <= self.max_width {
+            self.text = &self.text[(end + 1).min(self.text.len())..];
+            return Some(candidate);
+        }
+
+        // Break after the last whole word that fits, or inside the first word if even that does not fit.
+        let mut split = None;
+        for (i, &c) in candidate.iter().enumerate() {
+            if c == b' ' {
+                if self.layout.measure(trim_end(&candidate[..i])).width > self.max_width {
+                    break;
+                }
+                split = Some(i);
+            }
+        }
+        let split = match split {
+            Some(i) if !trim_end(&candidate[..i]).is_empty() => i,
+            _ => {
+                let mut k: usize = 1;
+                while k < candidate.len()
+                    && self.layout.measure(&candidate[..k + 1]).width <= self.max_width
+                {
+                    k += 1;
+                }
+                k
+            }
+        };
+
+        let line = trim_end(&candidate[..split]);
+        let mut rest = &self.text[split..];
+        while rest.first() == Some(&b' ') {
+            rest = &rest[1..];
+        }
+        self.text = rest;
+        Some(line)
+    }
+}
+
+/// Draws `text` into `display` with the top-left corner of the first character at column `x`, row `y`, using
+/// [TextLayout::MONOSPACE]. Returns the column at which a following character would be drawn, or an error if the text
+/// does not fit. Nothing is drawn if the text does not fit.
+pub const fn draw_text(
+    display: &mut DisplayArr,
+    text: &[u8],
+    x: usize,
+    y: usize,
+) -> Result<usize, DisplayArrErr> {
+    TextLayout::MONOSPACE.draw(display, text, x, y)
+}
+
+/// Renders `text` into a new [DisplayArr]. Meant for `const` labels, where text that does not fit fails compilation.
+pub const fn pixelate_text(text: &[u8], x: usize, y: usize) -> DisplayArr {
+    pixelate_text_scaled(text, x, y, 1)
+}
+
+/// Renders `text` into a new [DisplayArr] like [pixelate_text], but `scale` times larger.
+pub const fn pixelate_text_scaled(text: &[u8], x: usize, y: usize, scale: usize) -> DisplayArr {
+    let mut output = DisplayArr::new();
+    match TextLayout::MONOSPACE.draw_scaled(&mut output, text, x, y, scale) {
+        Ok(_) => output,
+        Err(_) => panic!("text does not fit in a DisplayArr"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MPH: DisplayArr = pixelate_text(b"MPH", 0, 0);
+
+    /// Places glyph row bits with the leftmost pixel at column `x`.
+    const fn at(bits: u128, width: usize, x: usize) -> u128 {
+        bits << (128 - x - width)
+    }
+
+    #[test]
+    fn glyph_rows() {
+        let a = DisplayCharacter::new_from_ascii(b'A');
+        assert_eq!(a, DisplayCharacter::Standard(A_PIXELS));
+        let rows: [u8; 5] = [0b111, 0b101, 0b111, 0b101, 0b101];
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(a.row_bits(i), *row);
+        }
+
+        let m = DisplayCharacter::new_from_ascii(b'M');
+        assert_eq!(m.row_bits(0), 0b11011);
+        assert_eq!(m.row_bits(4), 0b10001);
+
+        let dot = DisplayCharacter::new_from_ascii(b'.');
+        assert_eq!(dot.row_bits(0), 0);
+        assert_eq!(dot.row_bits(2), 0);
+        assert_eq!(dot.row_bits(3), 0b0110);
+        assert_eq!(dot.row_bits(4), 0b0110);
+    }
+
+    #[test]
+    fn printable_ascii_complete() {
+        let unknown = DisplayCharacter::Standard(UNKNOWN_PIXELS);
+        for c in 0x20u8..=0x7E {
+            assert_ne!(
+                DisplayCharacter::new_from_ascii(c),
+                unknown,
+                "{:?} has no glyph",
+                c as char
+            );
+        }
+        assert_eq!(DisplayCharacter::new_from_ascii(0x7F), unknown);
+        assert_eq!(DisplayCharacter::new_from_ascii(b'\n'), unknown);
+    }
+
+    #[test]
+    fn lowercase_distinct() {
+        for c in b'a'..=b'z' {
+            let lower = DisplayCharacter::new_from_ascii(c);
+            assert_ne!(
+                lower,
+                DisplayCharacter::new_from_ascii(c.to_ascii_uppercase())
+            );
+            for other in (c + 1)..=b'z' {
+                assert_ne!(lower, DisplayCharacter::new_from_ascii(other));
+            }
+        }
+    }
+
+    #[test]
+    fn parens() {
+        let left = DisplayCharacter::new_from_ascii(b'(');
+        let right = DisplayCharacter::new_from_ascii(b')');
+        // `(` opens to the right, so its middle rows sit left of its top row.
+        assert_eq!(left.row_bits(0), 0b001);
+        assert_eq!(left.row_bits(2), 0b010);
+        assert_eq!(right.row_bits(0), 0b100);
+        assert_eq!(right.row_bits(2), 0b010);
+    }
+
+    #[test]
+    fn const_label() {
+        let mut runtime = DisplayArr::new();
+        assert_eq!(draw_text(&mut runtime, b"MPH", 0, 0).unwrap(), 14);
+        assert_eq!(runtime, MPH);
+
+        let row0 = at(0b11011, 5, 0) | at(0b111, 3, 6) | at(0b101, 3, 10);
+        let row4 = at(0b10001, 5, 0) | at(0b100, 3, 6) | at(0b101, 3, 10);
+        assert_eq!(MPH.row(0).unwrap(), row0);
+        assert_eq!(MPH.row(4).unwrap(), row4);
+        for i in 5..64 {
+            assert_eq!(MPH.row(i).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn text_position() {
+        let mut display = DisplayArr::new();
+        draw_text(&mut display, b"1", 70, 20).unwrap();
+        assert_eq!(display.row(20).unwrap(), at(0b110, 3, 70));
+        assert_eq!(display.row(24).unwrap(), at(0b111, 3, 70));
+        assert_eq!(display.row(19).unwrap(), 0);
+        assert_eq!(display.row(25).unwrap(), 0);
+    }
+
+    #[test]
+    fn text_bounds() {
+        let mut display = DisplayArr::new();
+        assert!(draw_text(&mut display, b"A", 125, 0).is_ok());
+        assert!(draw_text(&mut display, b"A", 126, 0).is_err());
+        assert!(draw_text(&mut display, b"A", 0, 59).is_ok());
+        assert!(draw_text(&mut display, b"A", 0, 60).is_err());
+
+        let before = display;
+        assert!(matches!(
+            draw_text(&mut display, b"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", 0, 10),
+            Err(DisplayArrErr::TextOverflowError(_))
+        ));
+        assert_eq!(display, before);
+    }
+
+    #[test]
+    fn measure() {
+        let mono = TextLayout::MONOSPACE;
+        let prop = TextLayout::PROPORTIONAL;
+        assert_eq!(
+            mono.measure(b""),
+            TextMetrics {
+                width: 0,
+                height: 0
+            }
+        );
+        assert_eq!(
+            mono.measure(b"MPH"),
+            TextMetrics {
+                width: 13,
+                height: 5
+            }
+        );
+        assert_eq!(mono.measure(b"i.i").width, 3 + 1 + 4 + 1 + 3);
+        // `i` is a single column wide and `.` two columns wide once their blank columns are dropped.
+        assert_eq!(prop.measure(b"i.i").width, 1 + 1 + 2 + 1 + 1);
+        assert_eq!(prop.measure(b"a b").width, 3 + 1 + 2 + 1 + 3);
+        assert_eq!(prop.measure(b"LT").width, 3 + 3);
+        assert_eq!(prop.measure(b"TL").width, 3 + 1 + 3);
+
+        for text in [&b"speed 88 MPH"[..], b"LT.", b"[{wide}]"] {
+            let mut display = DisplayArr::new();
+            let width = prop.measure(text).width;
+            assert_eq!(
+                prop.draw(&mut display, text, 10, 0).unwrap(),
+                10 + width + 1
+            );
+            for row in 0..5 {
+                // Nothing is drawn past the measured width.
+                assert_eq!(display.row(row).unwrap() & (u128::MAX >> (10 + width)), 0);
+            }
+            assert_ne!(display.row(0).unwrap() | display.row(4).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn proportional_draw() {
+        let mut display = DisplayArr::new();
+        assert_eq!(
+            TextLayout::PROPORTIONAL
+                .draw(&mut display, b"i!", 0, 0)
+                .unwrap(),
+            4
+        );
+        // Both are single-column glyphs drawn from their middle column.
+        assert_eq!(display.row(0).unwrap(), at(0b101, 3, 0));
+        assert_eq!(display.row(1).unwrap(), at(0b001, 3, 0));
+        assert_eq!(display.row(2).unwrap(), at(0b101, 3, 0));
+
+        let mut display = DisplayArr::new();
+        assert!(TextLayout::PROPORTIONAL
+            .draw(&mut display, b"iii", 123, 0)
+            .is_ok());
+        assert!(matches!(
+            TextLayout::PROPORTIONAL.draw(&mut display, b"iii", 124, 0),
+            Err(DisplayArrErr::TextOverflowError(_))
+        ));
+    }
+
+    #[test]
+    fn default_kerning_does_not_touch() {
+        let layout = TextLayout::PROPORTIONAL;
+        for pair in DEFAULT_KERNING {
+            let left = DisplayCharacter::new_from_ascii(pair.left);
+            let right = DisplayCharacter::new_from_ascii(pair.right);
+            let (left_skip, left_ink) = left.ink_bounds();
+            let (right_skip, _) = right.ink_bounds();
+            let gap = layout.advance(pair.left, pair.right) as isize - left_ink as isize;
+            assert!(gap >= 0);
+            for row in 0..LINE_HEIGHT {
+                let left_edge = left.row_bits(row) >> (left.width() - left_skip - left_ink) & 1;
+                let right_edge = right.row_bits(row) >> (right.width() - right_skip - 1) & 1;
+                assert!(
+                    gap > 0 || left_edge & right_edge == 0,
+                    "{:?} and {:?} touch",
+                    pair.left as char,
+                    pair.right as char
+                );
+            }
+        }
+    }
+
+    fn mono_box(bounds: Rect) -> TextBox<'static> {
+        TextBox {
+            layout: TextLayout::MONOSPACE,
+            ..TextBox::new(bounds)
+        }
+    }
+
+    #[test]
+    fn box_alignment() {
+        for (alignment, x) in [
+            (Alignment::Left, 10),
+            (Alignment::Center, 26),
+            (Alignment::Right, 43),
+        ] {
+            let text_box = TextBox {
+                alignment,
+                ..mono_box(Rect::new(10, 3, 40, 5))
+            };
+            let mut display = DisplayArr::new();
+            assert_eq!(text_box.draw(&mut display, b"AB").unwrap(), 1);
+
+            let mut expected = DisplayArr::new();
+            draw_text(&mut expected, b"AB", x, 3).unwrap();
+            assert_eq!(display, expected, "{:?}", alignment);
+        }
+    }
+
+    #[test]
+    fn box_wrap() {
+        let text_box = mono_box(Rect::new(0, 0, 24, 64));
+        let lines: Vec<&[u8]> = text_box.lines(b"SPEED LIMIT 65").collect();
+        assert_eq!(lines, [&b"SPEED"[..], b"LIMIT", b"65"]);
+        let lines: Vec<&[u8]> = text_box.lines(b"SPEED  65\n\nMPH").collect();
+        assert_eq!(lines, [&b"SPEED"[..], b"65", b"", b"MPH"]);
+        let lines: Vec<&[u8]> = mono_box(Rect::new(0, 0, 15, 64))
+            .lines(b"ABCDEFGH")
+            .collect();
+        assert_eq!(lines, [&b"ABCD"[..], b"EFGH"]);
+
+        let mut display = DisplayArr::new();
+        assert_eq!(text_box.draw(&mut display, b"SPEED LIMIT 65").unwrap(), 3);
+        let mut expected = DisplayArr::new();
+        draw_text(&mut expected, b"SPEED", 0, 0).unwrap();
+        draw_text(&mut expected, b"LIMIT", 0, 6).unwrap();
+        draw_text(&mut expected, b"65", 0, 12).unwrap();
+        assert_eq!(display, expected);
+    }
+
+    #[test]
+    fn box_ellipsis() {
+        let text_box = TextBox {
+            overflow: Overflow::Ellipsis,
+            ..mono_box(Rect::new(0, 0, 40, 10))
+        };
+        let mut display = DisplayArr::new();
+        assert_eq!(text_box.draw(&mut display, b"SPEED LIMIT 65").unwrap(), 1);
+        let mut expected = DisplayArr::new();
+        draw_text(&mut expected, b"SPEED...", 0, 0).unwrap();
+        assert_eq!(display, expected);
+
+        let text_box = TextBox {
+            wrap: false,
+            bounds: Rect::new(0, 0, 42, 10),
+            ..text_box
+        };
+        let mut display = DisplayArr::new();
+        assert_eq!(text_box.draw(&mut display, b"SPEED LIMIT 65").unwrap(), 1);
+        let mut expected = DisplayArr::new();
+        draw_text(&mut expected, b"SPEED L...", 0, 0).unwrap();
+        assert_eq!(display, expected);
+    }
+
+    #[test]
+    fn box_clip() {
+        let text_box = TextBox {
+            wrap: false,
+            ..mono_box(Rect::new(2, 1, 10, 3))
+        };
+        let mut display = DisplayArr::new();
+        assert_eq!(text_box.draw(&mut display, b"ABCDEF").unwrap(), 1);
+
+        let mut unclipped = DisplayArr::new();
+        draw_text(&mut unclipped, b"ABCDEF", 2, 1).unwrap();
+        let mask = DisplayArr::row_mask(2, 10);
+        for row in 0..64 {
+            let expected = if (1..4).contains(&row) {
+                unclipped.row(row).unwrap() & mask
+            } else {
+                0
+            };
+            assert_eq!(display.row(row).unwrap(), expected, "row {}", row);
+        }
+
+        let mut display = DisplayArr::new_full();
+        let text_box = TextBox {
+            clear: true,
+            ..text_box
+        };
+        text_box.draw(&mut display, b"").unwrap();
+        assert_eq!(display.row(0).unwrap(), u128::MAX);
+        assert_eq!(display.row(1).unwrap(), !mask);
+        assert_eq!(display.row(4).unwrap(), u128::MAX);
+
+        assert!(mono_box(Rect::new(100, 0, 29, 5))
+            .draw(&mut display, b"A")
+            .is_err());
+    }
+
+    #[test]
+    fn scale_bits_stretches() {
+        assert_eq!(scale_bits(0b101, 3, 1), 0b101);
+        assert_eq!(scale_bits(0b1101, 3, 1), 0b101);
+        assert_eq!(scale_bits(0b101, 3, 2), 0b110011);
+        assert_eq!(scale_bits(0b110, 3, 3), 0b111111000);
+        assert_eq!(scale_bits(1, 1, 128), u128::MAX);
+        assert_eq!(scale_bits(u128::MAX, 32, 4), u128::MAX);
+    }
+
+    #[test]
+    fn glyph_scaled() {
+        for character in *b"AM," {
+            let glyph = DisplayCharacter::new_from_ascii(character);
+            for scale in 1..=4 {
+                let mut display = DisplayArr::new();
+                glyph.draw_scaled(&mut display, 7, 2, scale).unwrap();
+                for row in 0..64 {
+                    let mut expected = 0u128;
+                    if (2..2 + LINE_HEIGHT * scale).contains(&row) {
+                        let bits = glyph.row_bits((row - 2) / scale);
+                        for column in 0..glyph.width() {
+                            if bits & (1 << (glyph.width() - 1 - column)) != 0 {
+                                expected |= DisplayArr::row_mask(7 + column * scale, scale);
+                            }
+                        }
+                    }
+                    assert_eq!(display.row(row).unwrap(), expected, "{} row {}", scale, row);
+                }
+            }
+        }
+
+        let mut display = DisplayArr::new();
+        let glyph = DisplayCharacter::new_from_ascii(b'W');
+        assert!(glyph.draw_scaled(&mut display, 108, 0, 4).is_ok());
+        assert!(glyph.draw_scaled(&mut display, 109, 0, 4).is_err());
+        assert!(glyph.draw_scaled(&mut display, 0, 44, 4).is_ok());
+        assert!(glyph.draw_scaled(&mut display, 0, 45, 4).is_err());
+    }
+
+    #[test]
+    fn text_scaled() {
+        const MPH_2X: DisplayArr = pixelate_text_scaled(b"MPH", 0, 0, 2);
+        let mut display = DisplayArr::new();
+        for (i, character) in b"MPH".iter().enumerate() {
+            let glyph = DisplayCharacter::new_from_ascii(*character);
+            glyph
+                .draw_scaled(&mut display, [0, 12, 20][i], 0, 2)
+                .unwrap();
+        }
+        assert_eq!(MPH_2X, display);
+        assert_eq!(
+            pixelate_text_scaled(b"MPH", 3, 4, 1),
+            pixelate_text(b"MPH", 3, 4)
+        );
+
+        let layout = TextLayout::PROPORTIONAL;
+        assert_eq!(
+            layout.measure_scaled(b"88 MPH", 3),
+            TextMetrics {
+                width: layout.measure(b"88 MPH").width * 3,
+                height: 15
+            }
+        );
+        let mut display = DisplayArr::new();
+        assert_eq!(
+            layout.draw_scaled(&mut display, b"i!", 10, 0, 4).unwrap(),
+            10 + 3 * 4 + 4
+        );
+        assert_eq!(
+            display.row(0).unwrap(),
+            DisplayArr::row_mask(10, 4) | DisplayArr::row_mask(18, 4)
+        );
+        assert_eq!(display.row(4).unwrap(), DisplayArr::row_mask(18, 4));
+
+        let mut display = DisplayArr::new();
+        assert!(matches!(
+            layout.draw_scaled(&mut display, b"SPEEDOMETER", 0, 0, 4),
+            Err(DisplayArrErr::TextOverflowError(_))
+        ));
+        assert!(layout.draw_scaled(&mut display, b"A", 0, 50, 3).is_err());
+        assert_eq!(display, DisplayArr::new());
+    }
+}
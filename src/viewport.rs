@@ -0,0 +1,599 @@
+use core::result::Result;
+use core::result::Result::Err;
+use core::result::Result::Ok;
+
+use crate::draw::{apply_row, normalise_arc, DrawMode, Shape};
+use crate::newspeed::{DisplayArr, DisplayArrErr, Rect};
+use crate::segment::{SegmentFont, SegmentReadout};
+use crate::sprite::{RasterOp, Sprite};
+use crate::text::{TextBox, TextLayout};
+
+/// A rectangle of a [DisplayArr] with its own origin, so a widget can draw without knowing where it is on the display.
+/// Coordinates are relative to the top-left corner of the viewport. Primitives which clip, such as lines and circles,
+/// are clipped to the viewport; the rest return [DisplayArrErr] if they would fall outside of it.
+pub struct Viewport<'a> {
+    display: &'a mut DisplayArr,
+    bounds: Rect,
+}
+
+impl<'a> Viewport<'a> {
+    /// Creates a viewport onto `bounds` of `display`. Returns [DisplayArrErr::InvalidColumnError] or
+    /// [DisplayArrErr::InvalidRowError] if `bounds` does not lie within `display`, or [DisplayArrErr::EmptyRectError]
+    /// if it covers no pixels.
+    pub const fn new(display: &'a mut DisplayArr, bounds: Rect) -> Result<Self, DisplayArrErr> {
+        if bounds.is_empty() {
+            return Err(DisplayArrErr::EmptyRectError(bounds));
+        }
+        if let Err(e) = DisplayArr::check_rect(&bounds) {
+            return Err(e);
+        }
+        Ok(Self { display, bounds })
+    }
+
+    /// Gets the area of the display the viewport covers, in display coordinates.
+    pub const fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub const fn width(&self) -> usize {
+        self.bounds.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.bounds.height
+    }
+
+    /// Creates a viewport onto `bounds` of `self`, in local coordinates. Returns [DisplayArrErr::InvalidColumnError] or
+    /// [DisplayArrErr::InvalidRowError] if `bounds` does not lie within `self`, or [DisplayArrErr::EmptyRectError] if
+    /// it covers no pixels.
+    pub fn viewport(&mut self, bounds: &Rect) -> Result<Viewport<'_>, DisplayArrErr> {
+        if bounds.is_empty() {
+            return Err(DisplayArrErr::EmptyRectError(*bounds));
+        }
+        self.check_rect(bounds)?;
+        let bounds = Rect::new(
+            self.bounds.x + bounds.x,
+            self.bounds.y + bounds.y,
+            bounds.width,
+            bounds.height,
+        );
+        Ok(Viewport {
+            display: self.display,
+            bounds,
+        })
+    }
+
+    /// Checks that `rect` lies entirely within `self`, in local coordinates. Returns
+    /// [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] for the first edge which does not.
+    pub const fn check_rect(&self, rect: &Rect) -> Result<(), DisplayArrErr> {
+        if rect.right() > self.bounds.width {
+            return Err(DisplayArrErr::column_err(
+                self.bounds.width - 1,
+                rect.right() - 1,
+            ));
+        }
+        if rect.bottom() > self.bounds.height {
+            return Err(DisplayArrErr::row_err(
+                self.bounds.height - 1,
+                rect.bottom() - 1,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Moves `rect` from local to display coordinates.
+    const fn to_display(&self, rect: &Rect) -> Rect {
+        Rect::new(
+            self.bounds.x + rect.x,
+            self.bounds.y + rect.y,
+            rect.width,
+            rect.height,
+        )
+    }
+
+    /// Gets the display column and row of the local point (`x`, `y`), which may lie outside of the viewport.
+    const fn offset(&self, x: isize, y: isize) -> (isize, isize) {
+        (x + self.bounds.x as isize, y + self.bounds.y as isize)
+    }
+
+    /// Gets the display row mask covering the viewport's columns.
+    const fn columns_mask(&self) -> u128 {
+        DisplayArr::row_mask(self.bounds.x, self.bounds.width)
+    }
+
+    /// Gets row `idx` of `self`, with local column 0 as the highest bit. Returns [DisplayArrErr::InvalidRowError] if
+    /// `idx` is out of bounds.
+    pub const fn row(&self, idx: usize) -> Result<u128, DisplayArrErr> {
+        if idx >= self.bounds.height {
+            return Err(DisplayArrErr::row_err(self.bounds.height - 1, idx));
+        }
+        match self.display.row(self.bounds.y + idx) {
+            Ok(row) => Ok((row & self.columns_mask()) << self.bounds.x),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sets row `idx` of `self`, with local column 0 as the highest bit of `src`. Bits past the viewport's width are
+    /// ignored. Returns [DisplayArrErr::InvalidRowError] if `idx` is out of bounds.
+    pub const fn set_row(&mut self, idx: usize, src: u128) -> Result<(), DisplayArrErr> {
+        if idx >= self.bounds.height {
+            return Err(DisplayArrErr::row_err(self.bounds.height - 1, idx));
+        }
+        let y = self.bounds.y + idx;
+        let row = match self.display.row(y) {
+            Ok(row) => row,
+            Err(e) => return Err(e),
+        };
+        let mask = self.columns_mask();
+        self.display
+            .set_row(y, (row & !mask) | ((src >> self.bounds.x) & mask))
+    }
+
+    /// Checks a local pixel, then gets its display row and column.
+    const fn pixel(
+        &self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(usize, usize), DisplayArrErr> {
+        if row_idx >= self.bounds.height {
+            return Err(DisplayArrErr::row_err(self.bounds.height - 1, row_idx));
+        }
+        if column_idx >= self.bounds.width {
+            return Err(DisplayArrErr::column_err(self.bounds.width - 1, column_idx));
+        }
+        Ok((self.bounds.y + row_idx, self.bounds.x + column_idx))
+    }
+
+    /// Gets a single pixel of `self`. Returns [DisplayArrErr::InvalidRowError] or [DisplayArrErr::InvalidColumnError]
+    /// if either index is out of bounds.
+    pub const fn bit(&self, row_idx: usize, column_idx: usize) -> Result<bool, DisplayArrErr> {
+        match self.pixel(row_idx, column_idx) {
+            Ok((y, x)) => self.display.bit(y, x),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Turns on a single pixel of `self`, with the same errors as [Self::bit].
+    pub const fn set_bit(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(), DisplayArrErr> {
+        match self.pixel(row_idx, column_idx) {
+            Ok((y, x)) => self.display.set_bit(y, x),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Turns off a single pixel of `self`, with the same errors as [Self::bit].
+    pub const fn clear_bit(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(), DisplayArrErr> {
+        match self.pixel(row_idx, column_idx) {
+            Ok((y, x)) => self.display.clear_bit(y, x),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Inverts a single pixel of `self`, with the same errors as [Self::bit].
+    pub const fn toggle_bit(
+        &mut self,
+        row_idx: usize,
+        column_idx: usize,
+    ) -> Result<(), DisplayArrErr> {
+        match self.pixel(row_idx, column_idx) {
+            Ok((y, x)) => self.display.toggle_bit(y, x),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Draws every pixel of `self` with `mode`, such as [DrawMode::Clear] to blank a widget before redrawing it.
+    pub const fn fill(&mut self, mode: DrawMode) {
+        let mask = self.columns_mask();
+        let mut y = self.bounds.y;
+        while y < self.bounds.bottom() {
+            // Rows within the bounds are always valid.
+            let _ = apply_row(self.display, y, mask, mode);
+            y += 1;
+        }
+    }
+
+    /// Collects pixels in a [Shape] clipped to `self`, then draws them with `mode`.
+    fn draw_shape(&mut self, mode: DrawMode, add: impl FnOnce(&mut Shape)) {
+        let mut shape = Shape::new(self.bounds);
+        add(&mut shape);
+        shape.draw(self.display, mode);
+    }
+
+    /// Draws a line like [crate::draw::draw_line], clipped to `self`.
+    pub fn draw_line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, mode: DrawMode) {
+        let ((x0, y0), (x1, y1)) = (self.offset(x0, y0), self.offset(x1, y1));
+        self.draw_shape(mode, |shape| shape.line(x0, y0, x1, y1));
+    }
+
+    /// Draws the outline of `rect` like [crate::draw::draw_rect]. Returns [DisplayArrErr::InvalidColumnError] or
+    /// [DisplayArrErr::InvalidRowError] if `rect` does not lie within `self`.
+    pub fn draw_rect(&mut self, rect: &Rect, mode: DrawMode) -> Result<(), DisplayArrErr> {
+        self.check_rect(rect)?;
+        let rect = self.to_display(rect);
+        self.draw_shape(mode, |shape| shape.rect(&rect));
+        Ok(())
+    }
+
+    /// Draws every pixel of `rect` like [crate::draw::fill_rect]. Returns [DisplayArrErr::InvalidColumnError] or
+    /// [DisplayArrErr::InvalidRowError] if `rect` does not lie within `self`.
+    pub fn fill_rect(&mut self, rect: &Rect, mode: DrawMode) -> Result<(), DisplayArrErr> {
+        self.check_rect(rect)?;
+        crate::draw::fill_rect(self.display, &self.to_display(rect), mode)
+    }
+
+    /// Draws the outline of a circle like [crate::draw::draw_circle], clipped to `self`.
+    pub fn draw_circle(&mut self, cx: isize, cy: isize, radius: isize, mode: DrawMode) {
+        let (cx, cy) = self.offset(cx, cy);
+        self.draw_shape(mode, |shape| shape.circle(cx, cy, radius, 0, 360, false));
+    }
+
+    /// Draws a filled circle like [crate::draw::fill_circle], clipped to `self`.
+    pub fn fill_circle(&mut self, cx: isize, cy: isize, radius: isize, mode: DrawMode) {
+        let (cx, cy) = self.offset(cx, cy);
+        self.draw_shape(mode, |shape| shape.circle(cx, cy, radius, 0, 360, true));
+    }
+
+    /// Draws the outline of an ellipse like [crate::draw::draw_ellipse], clipped to `self`.
+    pub fn draw_ellipse(&mut self, cx: isize, cy: isize, rx: isize, ry: isize, mode: DrawMode) {
+        let (cx, cy) = self.offset(cx, cy);
+        self.draw_shape(mode, |shape| shape.ellipse(cx, cy, rx, ry, false));
+    }
+
+    /// Draws a filled ellipse like [crate::draw::fill_ellipse], clipped to `self`.
+    pub fn fill_ellipse(&mut self, cx: isize, cy: isize, rx: isize, ry: isize, mode: DrawMode) {
+        let (cx, cy) = self.offset(cx, cy);
+        self.draw_shape(mode, |shape| shape.ellipse(cx, cy, rx, ry, true));
+    }
+
+    /// Draws part of a circle outline like [crate::draw::draw_arc], clipped to `self`.
+    pub fn draw_arc(
+        &mut self,
+        cx: isize,
+        cy: isize,
+        radius: isize,
+        start: i32,
+        sweep: i32,
+        mode: DrawMode,
+    ) {
+        let (cx, cy) = self.offset(cx, cy);
+        let (start, sweep) = normalise_arc(start, sweep);
+        self.draw_shape(mode, |shape| {
+            shape.circle(cx, cy, radius, start, sweep, false)
+        });
+    }
+
+    /// Draws `sprite` like [Sprite::blit], clipped to `self`.
+    pub fn blit(&mut self, sprite: &Sprite, x: isize, y: isize, op: RasterOp) {
+        let (x, y) = self.offset(x, y);
+        sprite.blit_clipped(self.display, x, y, op, &self.bounds);
+    }
+
+    /// Draws `text` with `layout` like [TextLayout::draw], with the top-left corner at local column `x`, row `y`.
+    /// Returns the local column at which a following character would be drawn. If the text does not fit in `self`,
+    /// nothing is drawn and [DisplayArrErr::TextOverflowError] or [DisplayArrErr::InvalidRowError] is returned.
+    pub fn draw_text(
+        &mut self,
+        layout: &TextLayout,
+        text: &[u8],
+        x: usize,
+        y: usize,
+    ) -> Result<usize, DisplayArrErr> {
+        self.draw_text_scaled(layout, text, x, y, 1)
+    }
+
+    /// Draws `text` like [Self::draw_text], but `scale` times larger like [TextLayout::draw_scaled]. Panics if `scale`
+    /// is 0.
+    pub fn draw_text_scaled(
+        &mut self,
+        layout: &TextLayout,
+        text: &[u8],
+        x: usize,
+        y: usize,
+        scale: usize,
+    ) -> Result<usize, DisplayArrErr> {
+        let metrics = layout.measure_scaled(text, scale);
+        self.check_text(metrics.width, metrics.height, x, y)?;
+        let next = layout.draw_scaled(
+            self.display,
+            text,
+            self.bounds.x + x,
+            self.bounds.y + y,
+            scale,
+        )?;
+        Ok(next - self.bounds.x)
+    }
+
+    /// Draws `text` in `text_box` like [TextBox::draw], with the box's bounds in local coordinates. Returns the number
+    /// of lines drawn, or [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if the box does not
+    /// lie within `self`.
+    pub fn draw_text_box(
+        &mut self,
+        text_box: &TextBox,
+        text: &[u8],
+    ) -> Result<usize, DisplayArrErr> {
+        self.check_rect(&text_box.bounds)?;
+        let text_box = TextBox {
+            bounds: self.to_display(&text_box.bounds),
+            ..*text_box
+        };
+        text_box.draw(self.display, text)
+    }
+
+    /// Checks that text `width` by `height` pixels at (`x`, `y`) fits in `self`.
+    const fn check_text(
+        &self,
+        width: usize,
+        height: usize,
+        x: usize,
+        y: usize,
+    ) -> Result<(), DisplayArrErr> {
+        if width == 0 {
+            return Ok(());
+        }
+        if x + width > self.bounds.width {
+            return Err(DisplayArrErr::text_overflow_err(
+                self.bounds.width - 1,
+                x + width - 1,
+            ));
+        }
+        self.check_rect(&Rect::new(0, y, 0, height))
+    }
+
+    /// Draws a seven-segment digit like [SegmentFont::draw_segments], with its top-left corner at local column `x`,
+    /// row `y`. Returns [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if the digit would fall
+    /// outside of `self`.
+    pub fn draw_segments(
+        &mut self,
+        font: &SegmentFont,
+        segments: u8,
+        x: usize,
+        y: usize,
+    ) -> Result<(), DisplayArrErr> {
+        self.check_rect(&Rect::new(x, y, font.width, font.height))?;
+        font.draw_segments(self.display, segments, self.bounds.x + x, self.bounds.y + y)
+    }
+
+    /// Draws `digit` like [SegmentFont::draw_digit], with the same errors as [Self::draw_segments].
+    pub fn draw_digit(
+        &mut self,
+        font: &SegmentFont,
+        digit: u8,
+        x: usize,
+        y: usize,
+    ) -> Result<(), DisplayArrErr> {
+        self.check_rect(&Rect::new(x, y, font.width, font.height))?;
+        font.draw_digit(self.display, digit, self.bounds.x + x, self.bounds.y + y)
+    }
+
+    /// Shows `value` like [SegmentReadout::update], with the readout's position in local coordinates. Returns
+    /// [DisplayArrErr::InvalidColumnError] or [DisplayArrErr::InvalidRowError] if the readout does not lie within
+    /// `self`, and nothing is drawn.
+    pub fn update_readout<const N: usize>(
+        &mut self,
+        readout: &mut SegmentReadout<N>,
+        value: u32,
+    ) -> Result<usize, DisplayArrErr> {
+        self.check_rect(&Rect::new(
+            readout.x,
+            readout.y,
+            readout.width(),
+            readout.font.height,
+        ))?;
+        readout.update_offset(self.display, value, self.bounds.x, self.bounds.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw;
+    use crate::text::draw_text;
+
+    #[test]
+    fn creation() {
+        let mut display = DisplayArr::new();
+        assert!(matches!(
+            Viewport::new(&mut display, Rect::new(100, 0, 29, 10)),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert!(matches!(
+            Viewport::new(&mut display, Rect::new(0, 60, 10, 5)),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+
+        let mut view = Viewport::new(&mut display, Rect::new(10, 20, 30, 10)).unwrap();
+        assert_eq!((view.width(), view.height()), (30, 10));
+        let inner = view.viewport(&Rect::new(5, 5, 10, 5)).unwrap();
+        assert_eq!(inner.bounds(), Rect::new(15, 25, 10, 5));
+        assert!(view.viewport(&Rect::new(25, 0, 10, 5)).is_err());
+
+        // Empty bounds are rejected, rather than making a viewport with no last row or column to report.
+        assert!(matches!(
+            view.viewport(&Rect::new(5, 5, 0, 5)),
+            Err(DisplayArrErr::EmptyRectError(_))
+        ));
+        assert!(matches!(
+            Viewport::new(&mut display, Rect::new(10, 20, 30, 0)),
+            Err(DisplayArrErr::EmptyRectError(_))
+        ));
+        let mut dot = Viewport::new(&mut display, Rect::new(0, 0, 1, 1)).unwrap();
+        assert!(dot.row(1).is_err());
+        assert!(dot.set_bit(0, 1).is_err());
+        assert!(dot.check_rect(&Rect::new(0, 0, 2, 1)).is_err());
+        assert!(dot.draw_text(&TextLayout::MONOSPACE, b"1", 0, 0).is_err());
+    }
+
+    #[test]
+    fn local_pixels_and_rows() {
+        let mut display = DisplayArr::new();
+        let mut view = Viewport::new(&mut display, Rect::new(60, 10, 8, 4)).unwrap();
+        view.set_bit(0, 0).unwrap();
+        view.set_bit(3, 7).unwrap();
+        view.toggle_bit(3, 7).unwrap();
+        view.toggle_bit(2, 7).unwrap();
+        assert!(view.bit(0, 0).unwrap());
+        assert!(matches!(
+            view.set_bit(4, 0),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+        assert!(matches!(
+            view.bit(0, 8),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+
+        // Rows are local, and bits past the viewport are ignored.
+        assert_eq!(view.row(0).unwrap(), 1 << 127);
+        view.set_row(1, u128::MAX).unwrap();
+        assert_eq!(view.row(1).unwrap(), 0xffu128 << 120);
+        view.clear_bit(1, 0).unwrap();
+
+        assert!(display.bit(10, 60).unwrap());
+        assert!(display.bit(12, 67).unwrap());
+        assert_eq!(display.row(11).unwrap(), DisplayArr::row_mask(61, 7));
+        assert!(!display.bit(13, 67).unwrap());
+    }
+
+    #[test]
+    fn primitives_are_offset() {
+        let mut expected = DisplayArr::new();
+        draw::draw_rect(&mut expected, &Rect::new(42, 21, 10, 6), DrawMode::Set).unwrap();
+        draw::draw_line(&mut expected, 41, 20, 60, 30, DrawMode::Xor);
+        draw::draw_arc(&mut expected, 50, 30, 5, 0, 180, DrawMode::Set);
+        draw_text(&mut expected, b"42", 45, 33).unwrap();
+
+        let mut display = DisplayArr::new();
+        let mut view = Viewport::new(&mut display, Rect::new(40, 19, 30, 30)).unwrap();
+        view.draw_rect(&Rect::new(2, 2, 10, 6), DrawMode::Set)
+            .unwrap();
+        view.draw_line(1, 1, 20, 11, DrawMode::Xor);
+        view.draw_arc(10, 11, 5, 0, 180, DrawMode::Set);
+        assert_eq!(
+            view.draw_text(&TextLayout::MONOSPACE, b"42", 5, 14)
+                .unwrap(),
+            13
+        );
+        assert_eq!(display, expected);
+    }
+
+    #[test]
+    fn text_and_readouts_are_offset() {
+        let text_box = TextBox::new(Rect::new(2, 1, 20, 12));
+        let mut readout = SegmentReadout::<2>::new(SegmentFont::new(5, 9, 1), 0, 14, 1);
+        let mut expected = DisplayArr::new();
+        TextLayout::MONOSPACE
+            .draw_scaled(&mut expected, b"7", 30, 5, 2)
+            .unwrap();
+        TextBox {
+            bounds: Rect::new(32, 6, 20, 12),
+            ..text_box
+        }
+        .draw(&mut expected, b"HELLO THERE")
+        .unwrap();
+        SegmentReadout::<2>::new(readout.font, 30, 19, 1)
+            .update(&mut expected, 42)
+            .unwrap();
+
+        let mut display = DisplayArr::new();
+        let mut view = Viewport::new(&mut display, Rect::new(30, 5, 40, 30)).unwrap();
+        assert_eq!(
+            view.draw_text_scaled(&TextLayout::MONOSPACE, b"7", 0, 0, 2)
+                .unwrap(),
+            8
+        );
+        assert!(matches!(
+            view.draw_text_scaled(&TextLayout::MONOSPACE, b"7", 0, 21, 2),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+        assert_eq!(view.draw_text_box(&text_box, b"HELLO THERE").unwrap(), 2);
+        assert_eq!(view.update_readout(&mut readout, 42).unwrap(), 2);
+        assert_eq!(display, expected);
+
+        let mut view = Viewport::new(&mut display, Rect::new(30, 5, 10, 10)).unwrap();
+        assert!(view.draw_text_box(&text_box, b"HI").is_err());
+        readout.invalidate();
+        assert!(matches!(
+            view.update_readout(&mut readout, 7),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert_eq!(display, expected);
+    }
+
+    #[test]
+    fn primitives_are_clipped() {
+        let mut display = DisplayArr::new();
+        let bounds = Rect::new(10, 10, 20, 20);
+        let mut view = Viewport::new(&mut display, bounds).unwrap();
+        view.fill_circle(10, 10, 40, DrawMode::Set);
+        view.draw_line(-50, 5, 100, 5, DrawMode::Xor);
+        view.fill_ellipse(0, 0, 3, 3, DrawMode::Xor);
+        view.draw_ellipse(19, 19, 5, 8, DrawMode::Clear);
+        view.draw_circle(0, 19, 4, DrawMode::Clear);
+        assert!(view
+            .fill_rect(&Rect::new(15, 0, 6, 1), DrawMode::Set)
+            .is_err());
+        assert!(view
+            .draw_rect(&Rect::new(0, 15, 1, 6), DrawMode::Set)
+            .is_err());
+
+        let mut outside = DisplayArr::new_full();
+        draw::fill_rect(&mut outside, &bounds, DrawMode::Clear).unwrap();
+        assert_eq!(display & outside, DisplayArr::new());
+        assert_eq!(display.row(15).unwrap(), 0);
+        assert_eq!(display.row(16).unwrap(), DisplayArr::row_mask(10, 20));
+    }
+
+    #[test]
+    fn widgets() {
+        const ROWS: [u64; 3] = [0b111, 0b101, 0b111];
+        let sprite = Sprite::new(3, 3, &ROWS);
+        let font = SegmentFont::new(5, 9, 1);
+
+        let mut display = DisplayArr::new_full();
+        let mut view = Viewport::new(&mut display, Rect::new(64, 0, 10, 10)).unwrap();
+        view.fill(DrawMode::Clear);
+        view.blit(&sprite, 8, -1, RasterOp::Or);
+        view.draw_digit(&font, 8, 0, 0).unwrap();
+        assert!(matches!(
+            view.draw_segments(&font, 0, 6, 0),
+            Err(DisplayArrErr::InvalidColumnError(_))
+        ));
+        assert!(matches!(
+            view.draw_text(&TextLayout::MONOSPACE, b"123", 0, 0),
+            Err(DisplayArrErr::TextOverflowError(_))
+        ));
+        assert!(matches!(
+            view.draw_text(&TextLayout::MONOSPACE, b"1", 0, 8),
+            Err(DisplayArrErr::InvalidRowError(_))
+        ));
+
+        // Copying a sprite over the edge of the viewport leaves the display outside of it alone.
+        let mut full = DisplayArr::new_full();
+        let mut corner = Viewport::new(&mut full, Rect::new(0, 0, 2, 2)).unwrap();
+        corner.blit(&sprite, 0, 0, RasterOp::Copy);
+        assert_eq!(full.row(1).unwrap(), !(1u128 << 126));
+        assert_eq!(full.row(2).unwrap(), u128::MAX);
+
+        // The sprite is cut off by the right and top edges of the viewport, not of the display.
+        assert_eq!(view.row(0).unwrap() & (0b11 << 118), 0b10 << 118);
+        assert_eq!(view.row(1).unwrap() & (0b11 << 118), 0b11 << 118);
+        assert_eq!(
+            display.row(0).unwrap() & DisplayArr::row_mask(74, 54),
+            DisplayArr::row_mask(74, 54)
+        );
+
+        let mut expected = DisplayArr::new();
+        font.draw_digit(&mut expected, 8, 64, 0).unwrap();
+        assert_eq!(
+            display.row(4).unwrap() & DisplayArr::row_mask(64, 5),
+            expected.row(4).unwrap()
+        );
+    }
+}